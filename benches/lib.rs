@@ -41,6 +41,60 @@ fn bench_str_hardcode(b: &mut test::Bencher) {
     b.bytes = 5;
 }
 
+fn search_haystack(pattern: &[u8]) -> Vec<u8> {
+    let mut bytes: Vec<u8> = (0..1 << 20).map(|n| b'a' + (n % 23) as u8).collect();
+    bytes.extend_from_slice(pattern);
+    bytes
+}
+
+fn naive_find(bytes: &[u8], pattern: &[u8]) -> Option<usize> {
+    (0..bytes.len() - pattern.len() + 1)
+        .map(|n| bytes[n..].starts_with(pattern))
+        .position(|p| p)
+}
+
+#[bench]
+fn bench_pattern_search(b: &mut test::Bencher) {
+    let pattern = b"\r\n--boundary\r\n";
+    let bytes = search_haystack(pattern);
+    b.iter(|| {
+        black_box(
+            black_box(&bytes[..])
+                .read_with::<&[u8]>(&mut 0, Bytes::Delimiter(pattern))
+                .unwrap(),
+        )
+    });
+    b.bytes = bytes.len() as u64;
+}
+
+#[bench]
+fn bench_pattern_search_naive(b: &mut test::Bencher) {
+    let pattern = b"\r\n--boundary\r\n";
+    let bytes = search_haystack(pattern);
+    b.iter(|| black_box(naive_find(black_box(&bytes[..]), pattern).unwrap()));
+    b.bytes = bytes.len() as u64;
+}
+
+#[bench]
+fn bench_delimiter_search(b: &mut test::Bencher) {
+    let bytes = search_haystack(b"\0");
+    b.iter(|| {
+        black_box(
+            black_box(&bytes[..])
+                .read_with::<&[u8]>(&mut 0, Bytes::Delimiter(b"\0"))
+                .unwrap(),
+        )
+    });
+    b.bytes = bytes.len() as u64;
+}
+
+#[bench]
+fn bench_delimiter_search_naive(b: &mut test::Bencher) {
+    let bytes = search_haystack(b"\0");
+    b.iter(|| black_box(black_box(&bytes[..]).iter().position(|c| *c == 0).unwrap()));
+    b.bytes = bytes.len() as u64;
+}
+
 #[bench]
 fn bench_example_read(b: &mut test::Bencher) {
     let bytes = black_box([0, 5, b"H"[0], b"E"[0], b"L"[0], b"L"[0], b"O"[0], 0]);
@@ -0,0 +1,206 @@
+use crate::{BytesExt, Error, Result, TryRead, TryWrite};
+use alloc::vec;
+use bytes::{Buf, BufMut};
+use std::io::IoSlice;
+
+/// Size of the stack buffer used for values spanning chunks.
+const STACK_LEN: usize = 64;
+
+/// Maximum number of chunks gathered into the stack buffer.
+const MAX_CHUNKS: usize = 16;
+
+/// Extension methods for reading from a [`Buf`](https://docs.rs/bytes/1/bytes/trait.Buf.html).
+///
+/// Values are read directly from the current chunk. A value spanning several
+/// chunks, e.g. in a `Chain`, is read from a copy of up to 64 bytes on the stack,
+/// gathered with `Buf::chunks_vectored`. Such values fail with `BadInput` if they
+/// don't fit the copy, or if the buffer doesn't override `chunks_vectored` to
+/// expose its chunks. The buffer is only advanced if reading succeeds.
+///
+/// Since the chunks don't outlive the buffer, only owned values can be read.
+///
+/// Requires the `bytes` feature.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use bytes::Buf;
+///
+/// let mut buf = (&[0xde, 0xad][..]).chain(&[0xbe, 0xef, 0x01][..]);
+///
+/// let num: u32 = buf.get_value_with(BE).unwrap();
+/// assert_eq!(num, 0xdeadbeef);
+/// let flag: bool = buf.get_value().unwrap();
+/// assert_eq!(flag, true);
+/// assert!(!buf.has_remaining());
+/// ```
+pub trait BufExt<Ctx> {
+    /// Reads a value from the buffer using the default context.
+    fn get_value<T>(&mut self) -> Result<T>
+    where
+        T: for<'b> TryRead<'b, Ctx>,
+        Ctx: Default + Clone,
+    {
+        self.get_value_with(Default::default())
+    }
+
+    /// Reads a value from the buffer specifying the context.
+    fn get_value_with<T>(&mut self, ctx: Ctx) -> Result<T>
+    where
+        T: for<'b> TryRead<'b, Ctx>,
+        Ctx: Clone;
+}
+
+impl<B, Ctx> BufExt<Ctx> for B
+where
+    B: Buf + ?Sized,
+{
+    fn get_value_with<T>(&mut self, ctx: Ctx) -> Result<T>
+    where
+        T: for<'b> TryRead<'b, Ctx>,
+        Ctx: Clone,
+    {
+        let remaining = self.remaining();
+        let chunk = self.chunk();
+        // A value ending before the end of the chunk can't depend on the chunks
+        // after it, otherwise it has to be read again from a copy.
+        match T::try_read(chunk, ctx.clone()) {
+            Ok((value, size)) if size < chunk.len() || chunk.len() == remaining => {
+                self.advance(size);
+                return Ok(value);
+            }
+            Err(Error::BadOffset(_)) if chunk.len() == remaining => return Err(Error::Incomplete),
+            Err(err) if chunk.len() == remaining => return Err(err),
+            _ => (),
+        }
+
+        let mut stack = [0u8; STACK_LEN];
+        let len = remaining.min(STACK_LEN);
+        if peek(self, &mut stack[..len]) < len {
+            return Err(Error::BadInput {
+                err: "Buffer doesn't expose enough chunks to copy from",
+            });
+        }
+
+        let truncated = len < remaining;
+        match T::try_read(&stack[..len], ctx) {
+            Ok((value, size)) if size < len || !truncated => {
+                self.advance(size);
+                Ok(value)
+            }
+            Err(Error::BadOffset(_)) | Err(Error::Incomplete) if !truncated => {
+                Err(Error::Incomplete)
+            }
+            // The value may continue past the copy.
+            Ok(_) | Err(Error::Incomplete) | Err(Error::BadOffset(_)) => Err(Error::BadInput {
+                err: "Value is too long to copy across chunks",
+            }),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Copies the head of the buffer into `dst` without advancing it, returning the
+/// number of bytes copied.
+///
+/// The chunks are taken from `Buf::chunks_vectored`, whose default implementation
+/// only exposes the current chunk, so fewer bytes may be copied than are remaining.
+fn peek<B: Buf + ?Sized>(buf: &B, dst: &mut [u8]) -> usize {
+    let mut chunks = [IoSlice::new(&[]); MAX_CHUNKS];
+    let count = buf.chunks_vectored(&mut chunks);
+
+    let offset = &mut 0;
+    for chunk in &chunks[..count] {
+        let len = chunk.len().min(dst.len() - *offset);
+        // Can't fail as `len` fits the remaining space.
+        let _ = dst.write_with(offset, &chunk[..len], ());
+        if *offset == dst.len() {
+            break;
+        }
+    }
+
+    *offset
+}
+
+/// Extension methods for writing into a [`BufMut`](https://docs.rs/bytes/1/bytes/trait.BufMut.html).
+///
+/// Values are first written into a scratch buffer, which starts with 64 bytes on
+/// the stack and grows on the heap until the value fits, and then copied into the
+/// buffer. As the value is written again whenever the scratch buffer grows, it must
+/// be `Clone`.
+///
+/// Requires the `bytes` feature.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+/// use bytes::BytesMut;
+///
+/// let mut buf = BytesMut::new();
+///
+/// buf.put_value_with(0xdeadbeef_u32, BE).unwrap();
+/// buf.put_value_with(Encoded("hello"), Str::Delimiter(NULL)).unwrap();
+/// assert_eq!(&buf[..], b"\xde\xad\xbe\xefhello\0");
+/// ```
+pub trait BufMutExt<Ctx> {
+    /// Writes a value into the buffer using the default context.
+    fn put_value<T>(&mut self, t: T) -> Result<()>
+    where
+        T: TryWrite<Ctx> + Clone,
+        Ctx: Default + Clone,
+    {
+        self.put_value_with(t, Default::default())
+    }
+
+    /// Writes a value into the buffer specifying the context.
+    fn put_value_with<T>(&mut self, t: T, ctx: Ctx) -> Result<()>
+    where
+        T: TryWrite<Ctx> + Clone,
+        Ctx: Clone;
+}
+
+impl<B, Ctx> BufMutExt<Ctx> for B
+where
+    B: BufMut + ?Sized,
+{
+    fn put_value_with<T>(&mut self, t: T, ctx: Ctx) -> Result<()>
+    where
+        T: TryWrite<Ctx> + Clone,
+        Ctx: Clone,
+    {
+        let mut stack = [0u8; STACK_LEN];
+        match t.clone().try_write(&mut stack, ctx.clone()) {
+            Ok(size) => return put(self, &stack[..size]),
+            Err(Error::Incomplete) | Err(Error::BadOffset(_)) => (),
+            Err(err) => return Err(err),
+        }
+
+        let mut len = STACK_LEN;
+        loop {
+            if len >= self.remaining_mut() {
+                return Err(Error::Incomplete);
+            }
+            len = len.saturating_mul(2).min(self.remaining_mut());
+
+            let mut heap = vec![0u8; len];
+            match t.clone().try_write(&mut heap, ctx.clone()) {
+                Ok(size) => return put(self, &heap[..size]),
+                Err(Error::Incomplete) | Err(Error::BadOffset(_)) => (),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/// Copies the written bytes into the buffer.
+fn put<B: BufMut + ?Sized>(buf: &mut B, bytes: &[u8]) -> Result<()> {
+    if buf.remaining_mut() < bytes.len() {
+        return Err(Error::Incomplete);
+    }
+    buf.put_slice(bytes);
+
+    Ok(())
+}
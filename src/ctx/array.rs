@@ -0,0 +1,28 @@
+use crate::{ReadIntoExt, Result, TryReadInto};
+
+impl<'a, T, Ctx, const N: usize> TryReadInto<'a, Ctx> for [T; N]
+where
+    T: TryReadInto<'a, Ctx>,
+    Ctx: Clone,
+{
+    #[inline]
+    fn try_read_into(&mut self, bytes: &'a [u8], ctx: Ctx) -> Result<usize> {
+        self[..].try_read_into(bytes, ctx)
+    }
+}
+
+impl<'a, T, Ctx> TryReadInto<'a, Ctx> for [T]
+where
+    T: TryReadInto<'a, Ctx>,
+    Ctx: Clone,
+{
+    #[inline]
+    fn try_read_into(&mut self, bytes: &'a [u8], ctx: Ctx) -> Result<usize> {
+        let offset = &mut 0;
+        for item in self {
+            bytes.read_into_with(offset, item, ctx.clone())?;
+        }
+
+        Ok(*offset)
+    }
+}
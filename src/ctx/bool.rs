@@ -1,4 +1,4 @@
-use crate::{check_len, Result, TryRead, TryWrite};
+use crate::{check_len, Encode, FixedSize, Result, TryRead, TryReadInto, TryWrite};
 
 impl<'a> TryRead<'a> for bool {
     #[inline]
@@ -19,3 +19,24 @@ impl TryWrite for bool {
         Ok(1)
     }
 }
+
+impl Encode for bool {
+    #[inline]
+    fn encode(&self, bytes: &mut [u8], ctx: ()) -> Result<usize> {
+        TryWrite::try_write(*self, bytes, ctx)
+    }
+}
+
+impl<'a> TryReadInto<'a> for bool {
+    #[inline]
+    fn try_read_into(&mut self, bytes: &'a [u8], ctx: ()) -> Result<usize> {
+        let (val, size) = TryRead::try_read(bytes, ctx)?;
+        *self = val;
+
+        Ok(size)
+    }
+}
+
+impl FixedSize for bool {
+    const SIZE: usize = 1;
+}
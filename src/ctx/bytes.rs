@@ -1,8 +1,10 @@
+use super::memmem::find;
 use crate::{check_len, Error, Result, TryRead, TryWrite};
 
 /// Context for &[u8] to determine where the slice ends.
 ///
-/// Pattern will be included in the result
+/// Pattern will be included in the result, while delimiter will **not**.
+/// Both of them will be consumed.
 ///
 /// # Example
 ///
@@ -15,64 +17,91 @@ use crate::{check_len, Error, Result, TryRead, TryWrite};
 /// let sub: &[u8] = bytes.read_with(&mut 0, Bytes::Len(2)).unwrap();
 /// assert_eq!(sub, &[0xde, 0xad]);
 ///
-/// static PATTERN: &'static [u8; 2] = &[0x00, 0xff];
+/// let pattern = [0x00, 0xff];
 ///
-/// let sub: &[u8] = bytes.read_with(&mut 0, Bytes::Pattern(PATTERN)).unwrap();
+/// let sub: &[u8] = bytes.read_with(&mut 0, Bytes::Pattern(&pattern)).unwrap();
 /// assert_eq!(sub, &[0xde, 0xad, 0xbe, 0xef, 0x00, 0xff]);
 ///
-/// let sub: &[u8] = bytes.read_with(&mut 0, Bytes::PatternUntil(PATTERN, 4)).unwrap();
+/// let sub: &[u8] = bytes.read_with(&mut 0, Bytes::PatternUntil(&pattern, 4)).unwrap();
 /// assert_eq!(sub, &[0xde, 0xad, 0xbe, 0xef]);
+///
+/// let offset = &mut 0;
+/// let sub: &[u8] = bytes.read_with(offset, Bytes::Delimiter(&pattern)).unwrap();
+/// assert_eq!(sub, &[0xde, 0xad, 0xbe, 0xef]);
+/// assert_eq!(*offset, 6);
 /// ```
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
-pub enum Bytes {
+pub enum Bytes<'p> {
     /// Take fix-length bytes
     Len(usize),
     /// Take bytes until reaching a byte pattern
-    Pattern(&'static [u8]),
+    Pattern(&'p [u8]),
     /// Take bytes until either byte pattern or length reached
-    PatternUntil(&'static [u8], usize),
+    PatternUntil(&'p [u8], usize),
+    /// Take bytes until reaching a byte pattern, excluding the pattern from the result
+    Delimiter(&'p [u8]),
+    /// Take bytes until either byte pattern or length reached, excluding the pattern
+    /// from the result
+    DelimiterUntil(&'p [u8], usize),
 }
 
-impl<'a> TryRead<'a, Bytes> for &'a [u8] {
+impl<'a> TryRead<'a, Bytes<'_>> for &'a [u8] {
     #[inline]
     fn try_read(bytes: &'a [u8], ctx: Bytes) -> Result<(Self, usize)> {
-        let len = match ctx {
-            Bytes::Len(len) => check_len(bytes, len)?,
+        let (len, size) = match ctx {
+            Bytes::Len(len) => {
+                let len = check_len(bytes, len)?;
+                (len, len)
+            }
             Bytes::Pattern(pattern) => {
-                if pattern.is_empty() {
-                    return Err(Error::BadInput {
-                        err: "Pattern is empty",
-                    });
-                }
-                check_len(bytes, pattern.len())?;
-                (0..bytes.len() - pattern.len() + 1)
-                    .map(|n| bytes[n..].starts_with(pattern))
-                    .position(|p| p)
-                    .map(|len| len + pattern.len())
-                    .ok_or(Error::Incomplete)?
+                let position = search(bytes, pattern, None)?.ok_or(Error::Incomplete)?;
+                (position + pattern.len(), position + pattern.len())
             }
-            Bytes::PatternUntil(pattern, len) => {
-                if pattern.is_empty() {
-                    return Err(Error::BadInput {
-                        err: "Pattern is empty",
-                    });
-                }
-                if pattern.len() > len {
-                    return Err(Error::BadInput {
-                        err: "Pattern is longer than restricted length",
-                    });
-                }
-                check_len(bytes, pattern.len())?;
-                (0..bytes.len() - pattern.len() + 1)
-                    .map(|n| bytes[n..].starts_with(pattern))
-                    .take(len - pattern.len())
-                    .position(|p| p)
-                    .map(|position| position + pattern.len())
-                    .unwrap_or(check_len(bytes, len)?)
+            Bytes::PatternUntil(pattern, len) => match search(bytes, pattern, Some(len))? {
+                Some(position) => (position + pattern.len(), position + pattern.len()),
+                None => (len, len),
+            },
+            Bytes::Delimiter(pattern) => {
+                let position = search(bytes, pattern, None)?.ok_or(Error::Incomplete)?;
+                (position, position + pattern.len())
             }
+            Bytes::DelimiterUntil(pattern, len) => match search(bytes, pattern, Some(len))? {
+                Some(position) => (position, position + pattern.len()),
+                None => (len, len),
+            },
         };
 
-        Ok((&bytes[..len], len))
+        Ok((&bytes[..len], size))
+    }
+}
+
+/// Searches for the first occurrence of `pattern` in `bytes`, or only in the first
+/// `limit` bytes if a limit is given.
+///
+/// Returns `Ok(None)` if the limit is reached before finding the pattern, and
+/// `Err(Error::Incomplete)` if the bytes end before either. A pattern longer than a
+/// non-zero limit is rejected, as it could never be found; a limit of 0 reads
+/// nothing, whatever the pattern.
+#[inline]
+pub(crate) fn search(bytes: &[u8], pattern: &[u8], limit: Option<usize>) -> Result<Option<usize>> {
+    if pattern.is_empty() {
+        return Err(Error::BadInput {
+            err: "Pattern is empty",
+        });
+    }
+
+    match limit {
+        None => find(bytes, pattern).map(Some).ok_or(Error::Incomplete),
+        Some(limit) if limit != 0 && pattern.len() > limit => Err(Error::BadInput {
+            err: "Pattern is longer than restricted length",
+        }),
+        Some(limit) => match find(&bytes[..limit.min(bytes.len())], pattern) {
+            Some(position) => Ok(Some(position)),
+            None => {
+                check_len(bytes, limit)?;
+                Ok(None)
+            }
+        },
     }
 }
 
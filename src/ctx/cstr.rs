@@ -0,0 +1,108 @@
+use crate::{check_len, Error, Result, TryRead, TryWrite};
+use core::ffi::CStr;
+
+#[cfg(feature = "alloc")]
+use alloc::ffi::CString;
+
+/// Context for `&CStr` to determine where the NUL terminator must appear.
+///
+/// The NUL terminator **will** be included in the result, as `CStr` holds it.
+///
+/// Default to `Nul::Unbounded`.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+/// use core::ffi::CStr;
+///
+/// let bytes: &[u8] = b"hello\0world";
+///
+/// let offset = &mut 0;
+/// let str: &CStr = bytes.read(offset).unwrap();
+/// assert_eq!(str.to_bytes(), b"hello");
+/// assert_eq!(*offset, 6);
+///
+/// let str: &CStr = bytes.read_with(&mut 0, Nul::Bounded(6)).unwrap();
+/// assert_eq!(str.to_bytes(), b"hello");
+/// assert!(bytes.read_with::<&CStr>(&mut 0, Nul::Bounded(5)).is_err());
+///
+/// let mut write = [0u8; 6];
+/// write.write(&mut 0, str).unwrap();
+/// assert_eq!(&write, b"hello\0");
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum Nul {
+    /// Take bytes until reaching the NUL terminator
+    #[default]
+    Unbounded,
+    /// Take bytes until reaching the NUL terminator, which must appear within the
+    /// given number of bytes
+    Bounded(usize),
+}
+
+impl<'a> TryRead<'a, Nul> for &'a CStr {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Nul) -> Result<(Self, usize)> {
+        let position = match ctx {
+            Nul::Unbounded => bytes
+                .iter()
+                .position(|c| *c == 0)
+                .ok_or(Error::Incomplete)?,
+            Nul::Bounded(len) => match bytes.iter().take(len).position(|c| *c == 0) {
+                Some(position) => position,
+                None => {
+                    check_len(bytes, len)?;
+                    return Err(Error::BadInput {
+                        err: "CStr is longer than restricted length",
+                    });
+                }
+            },
+        };
+
+        match CStr::from_bytes_with_nul(&bytes[..position + 1]) {
+            Ok(str) => Ok((str, position + 1)),
+            Err(_) => Err(Error::BadInput { err: "CStr Error" }),
+        }
+    }
+}
+
+impl TryWrite<Nul> for &CStr {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Nul) -> Result<usize> {
+        let str_bytes = self.to_bytes_with_nul();
+
+        if let Nul::Bounded(len) = ctx {
+            if str_bytes.len() > len {
+                return Err(Error::BadInput {
+                    err: "CStr is longer than restricted length",
+                });
+            }
+        }
+
+        check_len(bytes, str_bytes.len())?;
+
+        bytes[..str_bytes.len()].clone_from_slice(str_bytes);
+
+        Ok(str_bytes.len())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> TryRead<'a, Nul> for CString {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Nul) -> Result<(Self, usize)> {
+        let (str, size) = <&CStr as TryRead<Nul>>::try_read(bytes, ctx)?;
+
+        Ok((str.into(), size))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryWrite<Nul> for CString {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Nul) -> Result<usize> {
+        TryWrite::try_write(self.as_c_str(), bytes, ctx)
+    }
+}
@@ -0,0 +1,129 @@
+use crate::ctx::{Endian, BE, LE};
+use crate::{check_len, BytesExt, Error, Result, TryRead, TryWrite};
+
+/// Context for `Endian` to detect the byte order declared at the start of the input,
+/// or to write the mark declaring it.
+///
+/// A magic number is detected as big endian if it reads as is in big endian, and as
+/// little endian if it reads byte-swapped. A magic that reads the same both ways
+/// can't tell the byte order and is rejected. A mismatch is reported as
+/// `Error::BadConst` with the magic read in big endian.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// // A little endian TIFF header
+/// let bytes: &[u8] = &[b'I', b'I', 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00];
+///
+/// let offset = &mut 0;
+/// let endian: Endian = bytes.read_with(offset, Detect::Tiff).unwrap();
+/// assert_eq!(endian, LE);
+/// bytes.read_with::<()>(offset, Const(42u16, endian)).unwrap();
+/// assert_eq!(bytes.read_with::<u32>(offset, endian).unwrap(), 8);
+///
+/// // A pcap header written by a little endian machine
+/// let bytes: &[u8] = &[0xd4, 0xc3, 0xb2, 0xa1];
+/// let endian: Endian = bytes.read_with(&mut 0, Detect::Magic32(0xa1b2c3d4)).unwrap();
+/// assert_eq!(endian, LE);
+///
+/// let mut write = [0u8; 2];
+/// write.write_with(&mut 0, BE, Detect::Bom).unwrap();
+/// assert_eq!(write, [0xfe, 0xff]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Detect {
+    /// UTF-16 byte order mark, `FE FF` or `FF FE`
+    Bom,
+    /// TIFF byte order, `MM` or `II`
+    Tiff,
+    /// 2-byte magic number as stored in big endian
+    Magic16(u16),
+    /// 4-byte magic number as stored in big endian
+    Magic32(u32),
+    /// 8-byte magic number as stored in big endian
+    Magic64(u64),
+}
+
+/// Detects the byte order of a magic read in big endian.
+#[inline]
+fn detect_magic(expected: u64, swapped: u64, found: u64) -> Result<Endian> {
+    if expected == swapped {
+        Err(Error::BadInput {
+            err: "Magic reads the same in both byte orders",
+        })
+    } else if found == expected {
+        Ok(BE)
+    } else if found == swapped {
+        Ok(LE)
+    } else {
+        Err(Error::BadConst { expected, found })
+    }
+}
+
+impl<'a> TryRead<'a, Detect> for Endian {
+    #[inline]
+    fn try_read(bytes: &'a [u8], detect: Detect) -> Result<(Self, usize)> {
+        let offset = &mut 0;
+
+        let endian = match detect {
+            Detect::Bom | Detect::Tiff => {
+                let (big, little) = match detect {
+                    Detect::Bom => ([0xfe, 0xff], [0xff, 0xfe]),
+                    _ => (*b"MM", *b"II"),
+                };
+                let mark = &bytes[..check_len(bytes, 2)?];
+                *offset = 2;
+
+                if mark == big {
+                    BE
+                } else if mark == little {
+                    LE
+                } else {
+                    return Err(Error::BadInput {
+                        err: "Unknown byte order mark",
+                    });
+                }
+            }
+            Detect::Magic16(magic) => detect_magic(
+                u64::from(magic),
+                u64::from(magic.swap_bytes()),
+                u64::from(bytes.read_with::<u16>(offset, BE)?),
+            )?,
+            Detect::Magic32(magic) => detect_magic(
+                u64::from(magic),
+                u64::from(magic.swap_bytes()),
+                u64::from(bytes.read_with::<u32>(offset, BE)?),
+            )?,
+            Detect::Magic64(magic) => detect_magic(
+                magic,
+                magic.swap_bytes(),
+                bytes.read_with::<u64>(offset, BE)?,
+            )?,
+        };
+
+        Ok((endian, *offset))
+    }
+}
+
+impl TryWrite<Detect> for Endian {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], detect: Detect) -> Result<usize> {
+        let offset = &mut 0;
+
+        match detect {
+            Detect::Bom => bytes.write_with(offset, 0xfeffu16, self)?,
+            Detect::Tiff => {
+                let mark: &[u8] = if self == BE { b"MM" } else { b"II" };
+                bytes.write_with(offset, mark, ())?
+            }
+            Detect::Magic16(magic) => bytes.write_with(offset, magic, self)?,
+            Detect::Magic32(magic) => bytes.write_with(offset, magic, self)?,
+            Detect::Magic64(magic) => bytes.write_with(offset, magic, self)?,
+        }
+
+        Ok(*offset)
+    }
+}
@@ -0,0 +1,221 @@
+use crate::ctx::Endian;
+use crate::{Encode, Error, FixedSize, Result, TryRead, TryReadInto, TryWrite};
+use core::fmt;
+
+/// Rounding of a float converted to fixed-point.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Rounding {
+    /// Round to nearest, ties to even
+    Nearest,
+    /// Round towards negative infinity
+    Floor,
+    /// Round towards positive infinity
+    Ceil,
+    /// Round towards zero
+    Zero,
+}
+
+/// Handling of a float out of the fixed-point range.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Overflow {
+    /// Clamp to the minimum or maximum value
+    Saturate,
+    /// Fail with `Error::Invalid`
+    Error,
+}
+
+/// Rounds `x`, which is not NaN, to an integer. `core` has no float rounding, so
+/// it is done by truncating through `i64`, which is exact below 2^52, the bound
+/// past which every `f64` is already an integer.
+#[inline]
+fn round(x: f64, rounding: Rounding) -> f64 {
+    if x.abs() >= 4503599627370496.0 {
+        return x;
+    }
+
+    let trunc = x as i64 as f64;
+    let floor = if trunc > x { trunc - 1.0 } else { trunc };
+
+    match rounding {
+        Rounding::Zero => trunc,
+        Rounding::Floor => floor,
+        Rounding::Ceil => {
+            if trunc < x {
+                trunc + 1.0
+            } else {
+                trunc
+            }
+        }
+        Rounding::Nearest => {
+            let diff = x - floor;
+            if diff > 0.5 || (diff == 0.5 && floor % 2.0 != 0.0) {
+                floor + 1.0
+            } else {
+                floor
+            }
+        }
+    }
+}
+
+/// A binary fixed-point number: the integer `I` scaled by 2^-`FRAC`.
+///
+/// It is read and written as its raw integer with the `Endian` context. Converting
+/// from a float rounds and handles overflow as configured; `to_f64()` is exact for
+/// raw integers of up to 32 bits, and `to_f32()` for up to 24 bits.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// // A Q15 sensor reading and a Q16.16 gain
+/// let bytes: &[u8] = &[0x40, 0x00, 0x00, 0x01, 0x80, 0x00];
+///
+/// let offset = &mut 0;
+/// let reading: Q15 = bytes.read_with(offset, BE).unwrap();
+/// let gain: Q16_16 = bytes.read_with(offset, BE).unwrap();
+/// assert_eq!(reading.to_f32(), 0.5);
+/// assert_eq!(gain.to_f64(), 1.5);
+///
+/// let half = Q15::from_f64(0.5, Rounding::Nearest, Overflow::Error).unwrap();
+/// assert_eq!(half, reading);
+/// assert!(Q15::from_f64(1.0, Rounding::Nearest, Overflow::Error).is_err());
+/// assert_eq!(Q15::from_f64(1.0, Rounding::Nearest, Overflow::Saturate).unwrap(), Q15::MAX);
+///
+/// let mut write = [0u8; 2];
+/// write.write_with(&mut 0, Q15::from_bits(-0x8000), LE).unwrap();
+/// assert_eq!(write, [0x00, 0x80]);
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Default)]
+pub struct Fixed<I, const FRAC: u32>(I);
+
+/// Signed fixed-point with 15 fractional bits, in [-1, 1)
+pub type Q15 = Fixed<i16, 15>;
+/// Signed fixed-point with 31 fractional bits, in [-1, 1)
+pub type Q31 = Fixed<i32, 31>;
+/// Signed fixed-point with 16 integer and 16 fractional bits
+pub type Q16_16 = Fixed<i32, 16>;
+
+macro_rules! fixed_impl {
+    ($ty: ty) => {
+        impl<const FRAC: u32> Fixed<$ty, FRAC> {
+            /// The smallest value
+            pub const MIN: Self = Fixed(<$ty>::MIN);
+            /// The largest value
+            pub const MAX: Self = Fixed(<$ty>::MAX);
+
+            /// 2^`FRAC` as a float, checking `FRAC` at compile time.
+            const SCALE: f64 = {
+                assert!(FRAC <= <$ty>::BITS, "FRAC must not exceed the bits of I");
+                f64::from_bits((FRAC as u64 + 1023) << 52)
+            };
+
+            /// Creates a value from its raw integer.
+            #[inline]
+            pub const fn from_bits(bits: $ty) -> Self {
+                Fixed(bits)
+            }
+
+            /// Returns the raw integer.
+            #[inline]
+            pub const fn to_bits(self) -> $ty {
+                self.0
+            }
+
+            /// Converts to `f32`, rounding to nearest if it doesn't fit.
+            #[inline]
+            pub fn to_f32(self) -> f32 {
+                // Scaling by a power of two is exact, so this rounds only once
+                self.0 as f32 / Self::SCALE as f32
+            }
+
+            /// Converts to `f64`, rounding to nearest if it doesn't fit.
+            #[inline]
+            pub fn to_f64(self) -> f64 {
+                self.0 as f64 / Self::SCALE
+            }
+
+            /// Converts from `f32`, see [`from_f64()`](#method.from_f64).
+            #[inline]
+            pub fn from_f32(x: f32, rounding: Rounding, overflow: Overflow) -> Result<Self> {
+                Self::from_f64(f64::from(x), rounding, overflow)
+            }
+
+            /// Converts from `f64` with the given rounding.
+            ///
+            /// Values out of range saturate or fail with `Error::Invalid` according to
+            /// `overflow`. NaN always fails.
+            pub fn from_f64(x: f64, rounding: Rounding, overflow: Overflow) -> Result<Self> {
+                if x.is_nan() {
+                    return Err(Error::Invalid {
+                        err: "Value is NaN",
+                    });
+                }
+
+                let x = round(x * Self::SCALE, rounding);
+                let min = <$ty>::MIN as f64;
+                // MAX itself may round up, but MAX + 1 is a power of two
+                let end = <$ty>::MAX as f64 + 1.0;
+
+                if x >= min && x < end {
+                    Ok(Fixed(x as $ty))
+                } else if overflow == Overflow::Saturate {
+                    Ok(if x < min { Self::MIN } else { Self::MAX })
+                } else {
+                    Err(Error::Invalid {
+                        err: "Value is out of fixed-point range",
+                    })
+                }
+            }
+        }
+
+        impl<const FRAC: u32> fmt::Display for Fixed<$ty, FRAC> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(&self.to_f64(), f)
+            }
+        }
+
+        impl<'a, const FRAC: u32> TryRead<'a, Endian> for Fixed<$ty, FRAC> {
+            #[inline]
+            fn try_read(bytes: &'a [u8], endian: Endian) -> Result<(Self, usize)> {
+                <$ty as TryRead<'a, Endian>>::try_read(bytes, endian)
+                    .map(|(val, size)| (Fixed(val), size))
+            }
+        }
+
+        impl<const FRAC: u32> TryWrite<Endian> for Fixed<$ty, FRAC> {
+            #[inline]
+            fn try_write(self, bytes: &mut [u8], endian: Endian) -> Result<usize> {
+                <$ty as TryWrite<Endian>>::try_write(self.0, bytes, endian)
+            }
+        }
+
+        impl<const FRAC: u32> Encode<Endian> for Fixed<$ty, FRAC> {
+            #[inline]
+            fn encode(&self, bytes: &mut [u8], endian: Endian) -> Result<usize> {
+                TryWrite::try_write(*self, bytes, endian)
+            }
+        }
+
+        impl<'a, const FRAC: u32> TryReadInto<'a, Endian> for Fixed<$ty, FRAC> {
+            #[inline]
+            fn try_read_into(&mut self, bytes: &'a [u8], endian: Endian) -> Result<usize> {
+                self.0.try_read_into(bytes, endian)
+            }
+        }
+
+        impl<const FRAC: u32> FixedSize for Fixed<$ty, FRAC> {
+            const SIZE: usize = <$ty as FixedSize>::SIZE;
+        }
+    };
+}
+
+fixed_impl!(u8);
+fixed_impl!(u16);
+fixed_impl!(u32);
+fixed_impl!(u64);
+fixed_impl!(i8);
+fixed_impl!(i16);
+fixed_impl!(i32);
+fixed_impl!(i64);
@@ -0,0 +1,235 @@
+use core::cmp::Ordering;
+use core::fmt;
+
+/// Rounds `value >> shift` to nearest, ties to even.
+#[inline]
+fn round_shift(value: u64, shift: u32) -> u64 {
+    let quotient = value >> shift;
+    let rem = value & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+
+    if rem > half || (rem == half && quotient & 1 == 1) {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
+
+/// Encodes `x` as a 16-bit float with the given number of mantissa and exponent
+/// bits, rounding to nearest even.
+#[inline]
+fn encode(x: f64, man_bits: u32, exp_bits: u32) -> u16 {
+    let max_exp = (1 << exp_bits) - 1;
+    let bias = (1 << (exp_bits - 1)) - 1;
+    let inf = (max_exp << man_bits) as u16;
+
+    let bits = x.to_bits();
+    let sign = ((bits >> 63) as u16) << 15;
+    let exp = ((bits >> 52) & 0x7ff) as i32;
+    let man = bits & ((1 << 52) - 1);
+
+    if exp == 0x7ff {
+        return if man == 0 {
+            sign | inf
+        } else {
+            // Quiet NaN, keeping the top of the payload
+            sign | inf | (1 << (man_bits - 1)) | (man >> (52 - man_bits)) as u16
+        };
+    }
+    if exp == 0 {
+        // Zero, or an f64 subnormal far below the smallest 16-bit subnormal
+        return sign;
+    }
+
+    let exp = exp - 1023 + bias;
+    if exp >= max_exp {
+        sign | inf
+    } else if exp > 0 {
+        // A carry out of the mantissa correctly bumps the exponent, up to infinity
+        let value = ((exp as u64) << 52) | man;
+        sign | round_shift(value, 52 - man_bits) as u16
+    } else {
+        let shift = (53 - man_bits as i32 - exp) as u32;
+        if shift > 63 {
+            sign
+        } else {
+            sign | round_shift(man | (1 << 52), shift) as u16
+        }
+    }
+}
+
+/// Decodes a 16-bit float with the given number of mantissa and exponent bits,
+/// which is always exact.
+#[inline]
+fn decode(bits: u16, man_bits: u32, exp_bits: u32) -> f64 {
+    let max_exp = (1 << exp_bits) - 1;
+    let bias = (1 << (exp_bits - 1)) - 1;
+
+    let sign = u64::from(bits >> 15) << 63;
+    let exp = (i32::from(bits) >> man_bits) & max_exp;
+    let man = u64::from(bits) & ((1 << man_bits) - 1);
+
+    if exp == max_exp {
+        f64::from_bits(sign | (0x7ff << 52) | (man << (52 - man_bits)))
+    } else if exp == 0 {
+        // Subnormal: man * 2^(1 - bias - man_bits)
+        let scale = f64::from_bits(((1 - bias - man_bits as i32 + 1023) as u64) << 52);
+        f64::from_bits(sign | (man as f64 * scale).to_bits())
+    } else {
+        f64::from_bits(sign | (((exp - bias + 1023) as u64) << 52) | (man << (52 - man_bits)))
+    }
+}
+
+macro_rules! half_impl {
+    ($ty: ident, $man_bits: expr, $exp_bits: expr) => {
+        impl $ty {
+            /// Positive infinity
+            pub const INFINITY: $ty = $ty(((1 << $exp_bits) - 1) << $man_bits);
+            /// Negative infinity
+            pub const NEG_INFINITY: $ty = $ty(0x8000 | Self::INFINITY.0);
+            /// Not a number
+            pub const NAN: $ty = $ty(Self::INFINITY.0 | (1 << ($man_bits - 1)));
+
+            /// Creates a value from its raw bits.
+            #[inline]
+            pub const fn from_bits(bits: u16) -> Self {
+                $ty(bits)
+            }
+
+            /// Returns the raw bits.
+            #[inline]
+            pub const fn to_bits(self) -> u16 {
+                self.0
+            }
+
+            /// Converts from `f32`, rounding to nearest even.
+            #[inline]
+            pub fn from_f32(x: f32) -> Self {
+                // f32 to f64 is exact, so this rounds only once
+                Self::from_f64(f64::from(x))
+            }
+
+            /// Converts from `f64`, rounding to nearest even.
+            #[inline]
+            pub fn from_f64(x: f64) -> Self {
+                $ty(encode(x, $man_bits, $exp_bits))
+            }
+
+            /// Converts to `f32` exactly.
+            #[inline]
+            pub fn to_f32(self) -> f32 {
+                self.to_f64() as f32
+            }
+
+            /// Converts to `f64` exactly.
+            #[inline]
+            pub fn to_f64(self) -> f64 {
+                decode(self.0, $man_bits, $exp_bits)
+            }
+
+            /// Returns `true` if the value is NaN.
+            #[inline]
+            pub fn is_nan(self) -> bool {
+                self.0 & 0x7fff > Self::INFINITY.0
+            }
+        }
+
+        impl From<$ty> for f32 {
+            #[inline]
+            fn from(x: $ty) -> f32 {
+                x.to_f32()
+            }
+        }
+
+        impl From<$ty> for f64 {
+            #[inline]
+            fn from(x: $ty) -> f64 {
+                x.to_f64()
+            }
+        }
+
+        impl PartialEq for $ty {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.to_f32() == other.to_f32()
+            }
+        }
+
+        impl PartialOrd for $ty {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.to_f32().partial_cmp(&other.to_f32())
+            }
+        }
+
+        impl fmt::Debug for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Debug::fmt(&self.to_f32(), f)
+            }
+        }
+
+        impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(&self.to_f32(), f)
+            }
+        }
+    };
+}
+
+/// IEEE 754 binary16 half-precision float, as used in GPU buffers and model weights.
+///
+/// Values are stored as raw bits, and compare as the floats they represent.
+/// Conversions from `f32` and `f64` round to nearest even, handling subnormals,
+/// infinities and NaN.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// let bytes: &[u8] = &[0x3c, 0x00, 0x7b, 0xff];
+///
+/// let offset = &mut 0;
+/// let one: F16 = bytes.read_with(offset, BE).unwrap();
+/// let max: F16 = bytes.read_with(offset, BE).unwrap();
+/// assert_eq!(one.to_f32(), 1.0);
+/// assert_eq!(max.to_f32(), 65504.0);
+///
+/// // Rounds to the nearest representable value
+/// assert_eq!(F16::from_f32(1.0004).to_f32(), 1.0);
+/// assert_eq!(F16::from_f32(65520.0), F16::INFINITY);
+///
+/// let mut write = [0u8; 2];
+/// write.write_with(&mut 0, F16::from_f32(-2.0), LE).unwrap();
+/// assert_eq!(write, [0x00, 0xc0]);
+/// ```
+#[derive(Copy, Clone, Default)]
+pub struct F16(u16);
+
+half_impl!(F16, 10, 5);
+
+/// bfloat16 brain float, an `f32` with its mantissa cut to 7 bits, as used in
+/// machine learning.
+///
+/// Values are stored as raw bits, and compare as the floats they represent.
+/// Conversions from `f32` and `f64` round to nearest even, handling subnormals,
+/// infinities and NaN.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// let bytes: &[u8] = &[0x80, 0x3f];
+///
+/// let one: BF16 = bytes.read_with(&mut 0, LE).unwrap();
+/// assert_eq!(one.to_f32(), 1.0);
+/// assert_eq!(BF16::from_f32(3.14159).to_f32(), 3.140625);
+/// assert!(BF16::from_f64(f64::NAN).is_nan());
+/// ```
+#[derive(Copy, Clone, Default)]
+pub struct BF16(u16);
+
+half_impl!(BF16, 7, 8);
@@ -0,0 +1,147 @@
+use super::seq::{read_seq, read_seq_into, split_seq, write_seq, write_seq_bytes};
+use crate::ctx::{Encoded, Seq, Str};
+use crate::{Encode, Error, Result, TryRead, TryReadInto, TryWrite};
+use ::heapless::{String, Vec};
+use core::str;
+
+const CAPACITY_EXCEEDED: Error = Error::BadInput {
+    err: "Capacity exceeded",
+};
+
+impl<'a, T, Ctx, const N: usize> TryRead<'a, Seq<Ctx>> for Vec<T, N>
+where
+    T: TryRead<'a, Ctx>,
+    Ctx: Clone,
+{
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Seq<Ctx>) -> Result<(Self, usize)> {
+        let mut vec = Vec::new();
+        let size = read_seq(bytes, ctx, |item| {
+            vec.push(item).map_err(|_item| CAPACITY_EXCEEDED)
+        })?;
+
+        Ok((vec, size))
+    }
+}
+
+impl<'a, T, Ctx, const N: usize> TryReadInto<'a, Seq<Ctx>> for Vec<T, N>
+where
+    T: TryRead<'a, Ctx> + TryReadInto<'a, Ctx>,
+    Ctx: Clone,
+{
+    #[inline]
+    fn try_read_into(&mut self, bytes: &'a [u8], ctx: Seq<Ctx>) -> Result<usize> {
+        let (len, size) = read_seq_into(bytes, ctx, self, |vec, item| {
+            vec.push(item).map_err(|_item| CAPACITY_EXCEEDED)
+        })?;
+        self.truncate(len);
+
+        Ok(size)
+    }
+}
+
+impl<T, Ctx, const N: usize> TryWrite<Seq<Ctx>> for Vec<T, N>
+where
+    T: TryWrite<Ctx>,
+    Ctx: Clone,
+{
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Seq<Ctx>) -> Result<usize> {
+        write_seq(bytes, ctx, self.len(), self)
+    }
+}
+
+impl<T, Ctx, const N: usize> Encode<Seq<Ctx>> for Vec<T, N>
+where
+    T: Encode<Ctx>,
+    Ctx: Clone,
+{
+    #[inline]
+    fn encode(&self, bytes: &mut [u8], ctx: Seq<Ctx>) -> Result<usize> {
+        write_seq(bytes, ctx, self.len(), self)
+    }
+}
+
+impl<'a, const N: usize> TryRead<'a, Seq> for String<N> {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Seq) -> Result<(Self, usize)> {
+        let (bytes, size) = split_seq(bytes, ctx)?;
+
+        match str::from_utf8(bytes) {
+            Ok(str) => Ok((to_string(str)?, size)),
+            Err(_) => Err(Error::BadInput { err: "UTF8 Error" }),
+        }
+    }
+}
+
+impl<'a, const N: usize> TryReadInto<'a, Seq> for String<N> {
+    #[inline]
+    fn try_read_into(&mut self, bytes: &'a [u8], ctx: Seq) -> Result<usize> {
+        let (bytes, size) = split_seq(bytes, ctx)?;
+
+        match str::from_utf8(bytes) {
+            Ok(str) => {
+                self.clear();
+                self.push_str(str).map_err(|_| CAPACITY_EXCEEDED)?;
+                Ok(size)
+            }
+            Err(_) => Err(Error::BadInput { err: "UTF8 Error" }),
+        }
+    }
+}
+
+impl<const N: usize> TryWrite<Seq> for String<N> {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Seq) -> Result<usize> {
+        write_seq_bytes(bytes, ctx, self.as_bytes())
+    }
+}
+
+impl<const N: usize> Encode<Seq> for String<N> {
+    #[inline]
+    fn encode(&self, bytes: &mut [u8], ctx: Seq) -> Result<usize> {
+        write_seq_bytes(bytes, ctx, self.as_bytes())
+    }
+}
+
+impl<'a, const N: usize> TryRead<'a, Str<'_>> for String<N> {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Str) -> Result<(Self, usize)> {
+        let (str, size) = <&str as TryRead<Str>>::try_read(bytes, ctx)?;
+
+        Ok((to_string(str)?, size))
+    }
+}
+
+impl<'a, const N: usize> TryReadInto<'a, Str<'_>> for String<N> {
+    #[inline]
+    fn try_read_into(&mut self, bytes: &'a [u8], ctx: Str) -> Result<usize> {
+        let (str, size) = <&str as TryRead<Str>>::try_read(bytes, ctx)?;
+        self.clear();
+        self.push_str(str).map_err(|_| CAPACITY_EXCEEDED)?;
+
+        Ok(size)
+    }
+}
+
+impl<const N: usize> TryWrite<Str<'_>> for String<N> {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Str) -> Result<usize> {
+        TryWrite::try_write(Encoded(self.as_str()), bytes, ctx)
+    }
+}
+
+impl<const N: usize> Encode<Str<'_>> for String<N> {
+    #[inline]
+    fn encode(&self, bytes: &mut [u8], ctx: Str) -> Result<usize> {
+        TryWrite::try_write(Encoded(self.as_str()), bytes, ctx)
+    }
+}
+
+#[inline]
+fn to_string<const N: usize>(str: &str) -> Result<String<N>> {
+    let mut string = String::new();
+    string.push_str(str).map_err(|_| CAPACITY_EXCEEDED)?;
+
+    Ok(string)
+}
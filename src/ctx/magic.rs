@@ -0,0 +1,130 @@
+use crate::ctx::Endian;
+use crate::{check_len, Error, Result, TryRead, TryWrite};
+
+/// Context for `()` to read and verify, or write, constant magic bytes.
+///
+/// A mismatch is reported as `Error::BadMagic` with the first differing byte.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// const PNG: Magic = Magic(b"\x89PNG\r\n\x1a\n");
+///
+/// let bytes: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR";
+///
+/// let offset = &mut 0;
+/// bytes.read_with::<()>(offset, PNG).unwrap();
+/// assert_eq!(*offset, 8);
+/// assert_eq!(
+///     bytes.read_with::<()>(&mut 0, Magic(b"\x89PNG\r\n\n")),
+///     Err(Error::BadMagic { offset: 6, expected: b'\n', found: 0x1a })
+/// );
+///
+/// let mut write = [0u8; 8];
+/// write.write_with(&mut 0, (), PNG).unwrap();
+/// assert_eq!(write, bytes[..8]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Magic<'p>(pub &'p [u8]);
+
+impl<'a> TryRead<'a, Magic<'_>> for () {
+    #[inline]
+    fn try_read(bytes: &'a [u8], Magic(magic): Magic) -> Result<(Self, usize)> {
+        let len = check_len(bytes, magic.len())?;
+
+        match magic.iter().zip(bytes).position(|(a, b)| a != b) {
+            Some(offset) => Err(Error::BadMagic {
+                offset,
+                expected: magic[offset],
+                found: bytes[offset],
+            }),
+            None => Ok(((), len)),
+        }
+    }
+}
+
+impl TryWrite<Magic<'_>> for () {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], Magic(magic): Magic) -> Result<usize> {
+        TryWrite::try_write(magic, bytes, ())
+    }
+}
+
+/// Context for `()` to read and verify, or write, a constant number such as a
+/// version or a numeric magic.
+///
+/// A mismatch is reported as `Error::BadConst` with the expected and found values,
+/// zero-extended from their width, so `Const(-1i16, BE)` reports `0xffff`.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// const VERSION: Const<u16> = Const(2, BE);
+///
+/// let bytes: &[u8] = &[0x00, 0x02, 0x00, 0x03];
+///
+/// let offset = &mut 0;
+/// bytes.read_with::<()>(offset, VERSION).unwrap();
+/// assert_eq!(*offset, 2);
+/// assert_eq!(
+///     bytes.read_with::<()>(offset, VERSION),
+///     Err(Error::BadConst { expected: 2, found: 3 })
+/// );
+///
+/// let mut write = [0u8; 2];
+/// write.write_with(&mut 0, (), VERSION).unwrap();
+/// assert_eq!(write, bytes[..2]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Const<T>(pub T, pub Endian);
+
+macro_rules! const_impl {
+    ($ty: ty, $unsigned: ty) => {
+        impl<'a> TryRead<'a, Const<$ty>> for () {
+            #[inline]
+            fn try_read(
+                bytes: &'a [u8],
+                Const(expected, endian): Const<$ty>,
+            ) -> Result<(Self, usize)> {
+                let (found, size) = <$ty as TryRead<Endian>>::try_read(bytes, endian)?;
+
+                if found == expected {
+                    Ok(((), size))
+                } else {
+                    Err(Error::BadConst {
+                        expected: expected as $unsigned as u64,
+                        found: found as $unsigned as u64,
+                    })
+                }
+            }
+        }
+
+        impl TryWrite<Const<$ty>> for () {
+            #[inline]
+            fn try_write(
+                self,
+                bytes: &mut [u8],
+                Const(value, endian): Const<$ty>,
+            ) -> Result<usize> {
+                TryWrite::try_write(value, bytes, endian)
+            }
+        }
+    };
+}
+
+const_impl!(u8, u8);
+const_impl!(u16, u16);
+const_impl!(u32, u32);
+const_impl!(u64, u64);
+const_impl!(i8, u8);
+const_impl!(i16, u16);
+const_impl!(i32, u32);
+const_impl!(i64, u64);
+const_impl!(usize, usize);
+const_impl!(isize, usize);
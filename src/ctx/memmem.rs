@@ -0,0 +1,178 @@
+//! Substring search backing the pattern and delimiter contexts.
+//!
+//! Single bytes are searched a word at a time (SWAR), and longer patterns use the
+//! Two-Way algorithm by Crochemore and Perrin, which runs in linear time with
+//! constant space. Both are implemented in safe Rust.
+
+use core::cmp;
+use core::convert::TryInto;
+use core::mem;
+
+const WORD: usize = mem::size_of::<usize>();
+const LO: usize = usize::MAX / 0xff;
+const HI: usize = LO << 7;
+
+/// Returns the position of the first occurrence of `pattern` in `bytes`.
+#[inline]
+pub(crate) fn find(bytes: &[u8], pattern: &[u8]) -> Option<usize> {
+    match pattern {
+        [] => Some(0),
+        [byte] => memchr(*byte, bytes),
+        _ => two_way(bytes, pattern),
+    }
+}
+
+/// Returns the position of the first `needle` byte in `bytes`.
+#[inline]
+pub(crate) fn memchr(needle: u8, bytes: &[u8]) -> Option<usize> {
+    let repeated = LO * needle as usize;
+
+    let mut offset = 0;
+    for chunk in bytes.chunks_exact(WORD) {
+        let word = match chunk.try_into() {
+            Ok(word) => usize::from_ne_bytes(word),
+            Err(_) => break,
+        };
+        // A zero byte in `word ^ repeated` marks an occurrence of the needle.
+        let xor = word ^ repeated;
+        if xor.wrapping_sub(LO) & !xor & HI != 0 {
+            break;
+        }
+        offset += WORD;
+    }
+
+    bytes[offset..]
+        .iter()
+        .position(|b| *b == needle)
+        .map(|position| offset + position)
+}
+
+/// Returns the position of the first occurrence of `pattern` (at least two bytes
+/// long) in `bytes`.
+fn two_way(bytes: &[u8], pattern: &[u8]) -> Option<usize> {
+    let len = pattern.len();
+    if bytes.len() < len {
+        return None;
+    }
+
+    // A bloom filter of the pattern bytes, to skip windows whose last byte
+    // doesn't appear in the pattern at all.
+    let byteset = pattern.iter().fold(0u64, |set, b| set | (1 << (b & 0x3f)));
+    let contains = |b: u8| (byteset >> (b & 0x3f)) & 1 != 0;
+
+    let (crit_pos, period) = critical_factorization(pattern);
+
+    if pattern[..crit_pos] == pattern[period..period + crit_pos] {
+        // The pattern is periodic: remember how much of the left part is already
+        // known to match after shifting by the period.
+        let mut position = 0;
+        let mut memory = 0;
+        while position + len <= bytes.len() {
+            if !contains(bytes[position + len - 1]) {
+                position += len;
+                memory = 0;
+                continue;
+            }
+
+            let mut i = cmp::max(crit_pos, memory);
+            while i < len && pattern[i] == bytes[position + i] {
+                i += 1;
+            }
+            if i < len {
+                position += i - crit_pos + 1;
+                memory = 0;
+                continue;
+            }
+
+            let mut j = crit_pos;
+            while j > memory && pattern[j - 1] == bytes[position + j - 1] {
+                j -= 1;
+            }
+            if j <= memory {
+                return Some(position);
+            }
+
+            position += period;
+            memory = len - period;
+        }
+    } else {
+        // The pattern is not periodic: any shift up to this bound is safe.
+        let period = cmp::max(crit_pos, len - crit_pos) + 1;
+        let mut position = 0;
+        while position + len <= bytes.len() {
+            if !contains(bytes[position + len - 1]) {
+                position += len;
+                continue;
+            }
+
+            let mut i = crit_pos;
+            while i < len && pattern[i] == bytes[position + i] {
+                i += 1;
+            }
+            if i < len {
+                position += i - crit_pos + 1;
+                continue;
+            }
+
+            let mut j = crit_pos;
+            while j > 0 && pattern[j - 1] == bytes[position + j - 1] {
+                j -= 1;
+            }
+            if j == 0 {
+                return Some(position);
+            }
+
+            position += period;
+        }
+    }
+
+    None
+}
+
+/// Computes a critical factorization `(position, period)` of the pattern from the
+/// maximal suffixes for both byte orderings.
+fn critical_factorization(pattern: &[u8]) -> (usize, usize) {
+    let (crit_pos_less, period_less) = maximal_suffix(pattern, false);
+    let (crit_pos_greater, period_greater) = maximal_suffix(pattern, true);
+
+    if crit_pos_less > crit_pos_greater {
+        (crit_pos_less, period_less)
+    } else {
+        (crit_pos_greater, period_greater)
+    }
+}
+
+/// Computes the start position and period of the lexicographically maximal suffix,
+/// under the reversed ordering if `greater` is `false`.
+fn maximal_suffix(pattern: &[u8], greater: bool) -> (usize, usize) {
+    let mut left = 0;
+    let mut right = 1;
+    let mut offset = 0;
+    let mut period = 1;
+
+    while let Some(&a) = pattern.get(right + offset) {
+        let b = pattern[left + offset];
+        if (a < b && !greater) || (a > b && greater) {
+            // The suffix is smaller; the period is the whole prefix so far.
+            right += offset + 1;
+            offset = 0;
+            period = right - left;
+        } else if a == b {
+            // Advance through the repetition of the current period.
+            if offset + 1 == period {
+                right += offset + 1;
+                offset = 0;
+            } else {
+                offset += 1;
+            }
+        } else {
+            // The suffix is larger; start over from the current location.
+            left = right;
+            right += 1;
+            offset = 0;
+            period = 1;
+        }
+    }
+
+    (left, period)
+}
@@ -0,0 +1,152 @@
+use crate::{check_len, Error, FixedSize, Result, TryRead, TryWrite};
+use core::ops::Deref;
+
+/// Context for `Mixed<T>` giving the order of the 16-bit words of a number and of
+/// the bytes within each word.
+///
+/// Letters name the bytes of a 32-bit number from the most significant, so `ABCD`
+/// is big endian and `DCBA` is little endian. 64-bit numbers follow the same word
+/// and byte orders, e.g. `CDAB` stores `ABCDEFGH` as `GHEFCDAB`. Any other layout
+/// can be given as a `Custom` permutation.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Order {
+    /// Big endian words, big endian bytes
+    ABCD,
+    /// Little endian words, little endian bytes
+    DCBA,
+    /// Big endian words, little endian bytes, as in PDP-11 data and on some PLCs
+    BADC,
+    /// Little endian words, big endian bytes, as in Modbus
+    CDAB,
+    /// Any permutation, giving for each stored byte its index in big endian, e.g.
+    /// `[4, 5, 6, 7, 0, 1, 2, 3]` for 64-bit numbers stored as `EFGHABCD`.
+    ///
+    /// 32-bit numbers use the first 4 indices only. Indices that aren't a
+    /// permutation of the number's bytes are rejected with `Error::BadInput`.
+    Custom([u8; 8]),
+}
+
+impl Order {
+    /// Returns, for each of the `len` stored bytes, its index in big endian.
+    #[inline]
+    fn indices(self, len: usize) -> Result<[u8; 8]> {
+        let (reverse, swap) = match self {
+            Order::ABCD => (false, false),
+            Order::DCBA => (true, false),
+            Order::BADC => (false, true),
+            Order::CDAB => (true, true),
+            Order::Custom(indices) => return check_permutation(indices, len),
+        };
+
+        let mut indices = [0u8; 8];
+        for (i, index) in indices.iter_mut().enumerate().take(len) {
+            let i = if swap { i ^ 1 } else { i };
+            *index = if reverse { len - 1 - i } else { i } as u8;
+        }
+
+        Ok(indices)
+    }
+}
+
+/// Checks that the first `len` indices are a permutation of `0..len`.
+#[inline]
+fn check_permutation(indices: [u8; 8], len: usize) -> Result<[u8; 8]> {
+    let mut seen = 0u8;
+    for &index in &indices[..len] {
+        if index as usize >= len || seen & (1 << index) != 0 {
+            return Err(Error::BadInput {
+                err: "Invalid byte permutation",
+            });
+        }
+        seen |= 1 << index;
+    }
+
+    Ok(indices)
+}
+
+/// A 32-bit or 64-bit number stored in a mixed byte order, read and written with
+/// the [`Order`](enum.Order.html) context.
+///
+/// The wrapper keeps `Endian` the only context of the plain number types, so
+/// `read::<u32>()` still infers it.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// // 123.456f32 from a Modbus device: two big endian words, low word first
+/// let bytes: &[u8] = &[0xe9, 0x79, 0x42, 0xf6];
+///
+/// let value: Mixed<f32> = bytes.read_with(&mut 0, Order::CDAB).unwrap();
+/// assert_eq!(*value, 123.456);
+///
+/// let mut write = [0u8; 4];
+/// write.write_with(&mut 0, Mixed(0x0a0b0c0du32), Order::BADC).unwrap();
+/// assert_eq!(write, [0x0b, 0x0a, 0x0d, 0x0c]);
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Default)]
+pub struct Mixed<T>(pub T);
+
+impl<T> Mixed<T> {
+    /// Unwraps the number.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Mixed<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+macro_rules! mixed_impl {
+    ($ty: ty, $size: tt) => {
+        impl<'a> TryRead<'a, Order> for Mixed<$ty> {
+            #[inline]
+            fn try_read(bytes: &'a [u8], order: Order) -> Result<(Self, usize)> {
+                let indices = order.indices($size)?;
+                check_len(bytes, $size)?;
+
+                let mut buf = [0u8; $size];
+                for (byte, &index) in bytes[..$size].iter().zip(&indices) {
+                    buf[index as usize] = *byte;
+                }
+
+                Ok((Mixed(<$ty>::from_be_bytes(buf)), $size))
+            }
+        }
+
+        impl TryWrite<Order> for Mixed<$ty> {
+            #[inline]
+            fn try_write(self, bytes: &mut [u8], order: Order) -> Result<usize> {
+                let indices = order.indices($size)?;
+                check_len(bytes, $size)?;
+
+                let buf = self.0.to_be_bytes();
+                for (byte, &index) in bytes[..$size].iter_mut().zip(&indices) {
+                    *byte = buf[index as usize];
+                }
+
+                Ok($size)
+            }
+        }
+
+        impl FixedSize for Mixed<$ty> {
+            const SIZE: usize = $size;
+        }
+    };
+}
+
+mixed_impl!(u32, 4);
+mixed_impl!(i32, 4);
+mixed_impl!(f32, 4);
+mixed_impl!(u64, 8);
+mixed_impl!(i64, 8);
+mixed_impl!(f64, 8);
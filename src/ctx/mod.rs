@@ -1,10 +1,40 @@
 //! Context for primitives
 
+mod array;
 mod bool;
 mod bytes;
+mod cstr;
+mod detect;
+mod fixed;
+mod half;
+#[cfg(feature = "heapless")]
+mod heapless;
+mod magic;
+mod memmem;
+mod mixed;
 mod num;
+mod option;
+#[cfg(feature = "alloc")]
+mod owned;
+mod ptr;
+mod seq;
 mod str;
+mod text;
+mod utf16;
+mod validate;
 
 pub use self::bytes::*;
+pub use self::cstr::*;
+pub use self::detect::*;
+pub use self::fixed::*;
+pub use self::half::*;
+pub use self::magic::*;
+pub use self::mixed::*;
 pub use self::num::*;
+pub use self::option::*;
+pub use self::ptr::*;
+pub use self::seq::*;
 pub use self::str::*;
+pub use self::text::*;
+pub use self::utf16::*;
+pub use self::validate::*;
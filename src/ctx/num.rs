@@ -1,6 +1,7 @@
 #![allow(unused_parens)]
 
-use crate::{check_len, Error, Result, TryRead, TryWrite};
+use crate::ctx::{BF16, F16};
+use crate::{check_len, Encode, Error, FixedSize, Result, TryRead, TryReadInto, TryWrite};
 use core::convert::TryInto;
 use core::mem;
 
@@ -92,6 +93,26 @@ macro_rules! num_impl {
                 Ok($size)
             }
         }
+
+        impl Encode<Endian> for $ty {
+            #[inline]
+            fn encode(&self, bytes: &mut [u8], endian: Endian) -> Result<usize> {
+                TryWrite::try_write(*self, bytes, endian)
+            }
+        }
+
+        impl FixedSize for $ty {
+            const SIZE: usize = $size;
+        }
+
+        impl<'a> TryReadInto<'a, Endian> for $ty {
+            #[inline]
+            fn try_read_into(&mut self, bytes: &'a [u8], endian: Endian) -> Result<usize> {
+                let (val, size) = TryRead::try_read(bytes, endian)?;
+                *self = val;
+                Ok(size)
+            }
+        }
     };
 }
 
@@ -122,8 +143,30 @@ macro_rules! float_impl {
                 <$base as TryWrite<Endian>>::try_write(self.to_bits(), bytes, endian)
             }
         }
+
+        impl Encode<Endian> for $ty {
+            #[inline]
+            fn encode(&self, bytes: &mut [u8], endian: Endian) -> Result<usize> {
+                TryWrite::try_write(*self, bytes, endian)
+            }
+        }
+
+        impl FixedSize for $ty {
+            const SIZE: usize = <$base as FixedSize>::SIZE;
+        }
+
+        impl<'a> TryReadInto<'a, Endian> for $ty {
+            #[inline]
+            fn try_read_into(&mut self, bytes: &'a [u8], endian: Endian) -> Result<usize> {
+                let (val, size) = TryRead::try_read(bytes, endian)?;
+                *self = val;
+                Ok(size)
+            }
+        }
     };
 }
 
 float_impl!(f32, u32);
 float_impl!(f64, u64);
+float_impl!(F16, u16);
+float_impl!(BF16, u16);
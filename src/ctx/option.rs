@@ -0,0 +1,104 @@
+use crate::{BytesExt, Encode, Error, Result, TryRead, TryWrite};
+
+/// Context for `Option<T>` to determine whether the value is present.
+///
+/// The wrapped context is used for the value itself.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// let bytes: &[u8] = &[0x01, 0x00, 0x05, 0x00];
+///
+/// let offset = &mut 0;
+/// let some: Option<u16> = bytes.read_with(offset, Optional::Flag(BE)).unwrap();
+/// assert_eq!(some, Some(5));
+/// let none: Option<u16> = bytes.read_with(offset, Optional::Flag(BE)).unwrap();
+/// assert_eq!(none, None);
+/// assert_eq!(*offset, 4);
+///
+/// // Presence bit from an earlier flags field
+/// let flags = 0b10;
+/// let value: Option<u16> = bytes.read_with(&mut 1, Optional::When(flags & 0b10 != 0, BE)).unwrap();
+/// assert_eq!(value, Some(5));
+///
+/// let mut write = [0u8; 4];
+/// let offset = &mut 0;
+/// write.write_with(offset, Some(5u16), Optional::Flag(BE)).unwrap();
+/// write.write_with(offset, None::<u16>, Optional::When(false, BE)).unwrap();
+/// assert_eq!(*offset, 3);
+/// assert_eq!(write, [0xff, 0x00, 0x05, 0x00]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Optional<Ctx> {
+    /// A `bool` presence flag precedes the value
+    Flag(Ctx),
+    /// The value is present if the condition holds, e.g. a bit in an earlier flags field
+    When(bool, Ctx),
+}
+
+impl<'a, T, Ctx> TryRead<'a, Optional<Ctx>> for Option<T>
+where
+    T: TryRead<'a, Ctx>,
+{
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Optional<Ctx>) -> Result<(Self, usize)> {
+        let offset = &mut 0;
+
+        let (present, ctx) = match ctx {
+            Optional::Flag(ctx) => (bytes.read::<bool>(offset)?, ctx),
+            Optional::When(present, ctx) => (present, ctx),
+        };
+
+        let value = if present {
+            Some(bytes.read_with(offset, ctx)?)
+        } else {
+            None
+        };
+
+        Ok((value, *offset))
+    }
+}
+
+impl<T, Ctx> TryWrite<Optional<Ctx>> for Option<T>
+where
+    T: TryWrite<Ctx>,
+{
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Optional<Ctx>) -> Result<usize> {
+        let offset = &mut 0;
+
+        let ctx = match ctx {
+            Optional::Flag(ctx) => {
+                bytes.write(offset, self.is_some())?;
+                ctx
+            }
+            Optional::When(present, ctx) => {
+                if present != self.is_some() {
+                    return Err(Error::BadInput {
+                        err: "Optional presence mismatch",
+                    });
+                }
+                ctx
+            }
+        };
+
+        if let Some(value) = self {
+            bytes.write_with(offset, value, ctx)?;
+        }
+
+        Ok(*offset)
+    }
+}
+
+impl<T, Ctx> Encode<Optional<Ctx>> for Option<T>
+where
+    T: Encode<Ctx>,
+{
+    #[inline]
+    fn encode(&self, bytes: &mut [u8], ctx: Optional<Ctx>) -> Result<usize> {
+        TryWrite::try_write(self.as_ref(), bytes, ctx)
+    }
+}
@@ -0,0 +1,231 @@
+use super::seq::{read_seq, read_seq_into, split_seq, write_seq, write_seq_bytes};
+use crate::ctx::{Bytes, Encoded, Seq, Str};
+use crate::{Encode, Error, Result, TryRead, TryReadInto, TryWrite};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str;
+
+impl<'a, T, Ctx> TryRead<'a, Seq<Ctx>> for Vec<T>
+where
+    T: TryRead<'a, Ctx>,
+    Ctx: Clone,
+{
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Seq<Ctx>) -> Result<(Self, usize)> {
+        let mut vec = Vec::new();
+        let size = read_seq(bytes, ctx, |item| {
+            vec.push(item);
+            Ok(())
+        })?;
+
+        Ok((vec, size))
+    }
+}
+
+impl<'a, T, Ctx> TryReadInto<'a, Seq<Ctx>> for Vec<T>
+where
+    T: TryRead<'a, Ctx> + TryReadInto<'a, Ctx>,
+    Ctx: Clone,
+{
+    #[inline]
+    fn try_read_into(&mut self, bytes: &'a [u8], ctx: Seq<Ctx>) -> Result<usize> {
+        let (len, size) = read_seq_into(bytes, ctx, self, |vec, item| {
+            vec.push(item);
+            Ok(())
+        })?;
+        self.truncate(len);
+
+        Ok(size)
+    }
+}
+
+impl<T, Ctx> TryWrite<Seq<Ctx>> for Vec<T>
+where
+    T: TryWrite<Ctx>,
+    Ctx: Clone,
+{
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Seq<Ctx>) -> Result<usize> {
+        write_seq(bytes, ctx, self.len(), self)
+    }
+}
+
+impl<T, Ctx> Encode<Seq<Ctx>> for Vec<T>
+where
+    T: Encode<Ctx>,
+    Ctx: Clone,
+{
+    #[inline]
+    fn encode(&self, bytes: &mut [u8], ctx: Seq<Ctx>) -> Result<usize> {
+        write_seq(bytes, ctx, self.len(), self)
+    }
+}
+
+impl<'a> TryRead<'a, Seq> for String {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Seq) -> Result<(Self, usize)> {
+        let (str, size) = <Cow<str> as TryRead<Seq>>::try_read(bytes, ctx)?;
+
+        Ok((str.into_owned(), size))
+    }
+}
+
+impl<'a> TryReadInto<'a, Seq> for String {
+    #[inline]
+    fn try_read_into(&mut self, bytes: &'a [u8], ctx: Seq) -> Result<usize> {
+        let (str, size) = <Cow<str> as TryRead<Seq>>::try_read(bytes, ctx)?;
+        self.clear();
+        self.push_str(&str);
+
+        Ok(size)
+    }
+}
+
+impl TryWrite<Seq> for String {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Seq) -> Result<usize> {
+        write_seq_bytes(bytes, ctx, self.as_bytes())
+    }
+}
+
+impl Encode<Seq> for String {
+    #[inline]
+    fn encode(&self, bytes: &mut [u8], ctx: Seq) -> Result<usize> {
+        write_seq_bytes(bytes, ctx, self.as_bytes())
+    }
+}
+
+impl<'a> TryRead<'a, Str<'_>> for String {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Str) -> Result<(Self, usize)> {
+        let (str, size) = <&str as TryRead<Str>>::try_read(bytes, ctx)?;
+
+        Ok((str.into(), size))
+    }
+}
+
+impl<'a> TryReadInto<'a, Str<'_>> for String {
+    #[inline]
+    fn try_read_into(&mut self, bytes: &'a [u8], ctx: Str) -> Result<usize> {
+        let (str, size) = <&str as TryRead<Str>>::try_read(bytes, ctx)?;
+        self.clear();
+        self.push_str(str);
+
+        Ok(size)
+    }
+}
+
+impl TryWrite<Str<'_>> for String {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Str) -> Result<usize> {
+        TryWrite::try_write(Encoded(self.as_str()), bytes, ctx)
+    }
+}
+
+impl Encode<Str<'_>> for String {
+    #[inline]
+    fn encode(&self, bytes: &mut [u8], ctx: Str) -> Result<usize> {
+        TryWrite::try_write(Encoded(self.as_str()), bytes, ctx)
+    }
+}
+
+impl<'a, T, Ctx> TryRead<'a, Ctx> for Box<T>
+where
+    T: TryRead<'a, Ctx>,
+{
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Ctx) -> Result<(Self, usize)> {
+        let (value, size) = T::try_read(bytes, ctx)?;
+
+        Ok((Box::new(value), size))
+    }
+}
+
+impl<T, Ctx> TryWrite<Ctx> for Box<T>
+where
+    T: TryWrite<Ctx>,
+{
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Ctx) -> Result<usize> {
+        (*self).try_write(bytes, ctx)
+    }
+}
+
+impl<T, Ctx> Encode<Ctx> for Box<T>
+where
+    T: Encode<Ctx> + ?Sized,
+{
+    #[inline]
+    fn encode(&self, bytes: &mut [u8], ctx: Ctx) -> Result<usize> {
+        (**self).encode(bytes, ctx)
+    }
+}
+
+impl<'a> TryRead<'a, Seq> for Cow<'a, str> {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Seq) -> Result<(Self, usize)> {
+        let (bytes, size) = split_seq(bytes, ctx)?;
+
+        match str::from_utf8(bytes) {
+            Ok(str) => Ok((Cow::Borrowed(str), size)),
+            Err(_) => Err(Error::BadInput { err: "UTF8 Error" }),
+        }
+    }
+}
+
+impl TryWrite<Seq> for Cow<'_, str> {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Seq) -> Result<usize> {
+        write_seq_bytes(bytes, ctx, self.as_bytes())
+    }
+}
+
+impl<'a> TryRead<'a, Str<'_>> for Cow<'a, str> {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Str) -> Result<(Self, usize)> {
+        let (str, size) = <&str as TryRead<Str>>::try_read(bytes, ctx)?;
+
+        Ok((Cow::Borrowed(str), size))
+    }
+}
+
+impl TryWrite<Str<'_>> for Cow<'_, str> {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Str) -> Result<usize> {
+        TryWrite::try_write(Encoded(&self), bytes, ctx)
+    }
+}
+
+impl<'a> TryRead<'a, Seq> for Cow<'a, [u8]> {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Seq) -> Result<(Self, usize)> {
+        let (bytes, size) = split_seq(bytes, ctx)?;
+
+        Ok((Cow::Borrowed(bytes), size))
+    }
+}
+
+impl TryWrite<Seq> for Cow<'_, [u8]> {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Seq) -> Result<usize> {
+        write_seq_bytes(bytes, ctx, &self)
+    }
+}
+
+impl<'a> TryRead<'a, Bytes<'_>> for Cow<'a, [u8]> {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Bytes) -> Result<(Self, usize)> {
+        let (bytes, size) = <&[u8] as TryRead<Bytes>>::try_read(bytes, ctx)?;
+
+        Ok((Cow::Borrowed(bytes), size))
+    }
+}
+
+impl TryWrite for Cow<'_, [u8]> {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], _ctx: ()) -> Result<usize> {
+        TryWrite::try_write(&*self, bytes, ())
+    }
+}
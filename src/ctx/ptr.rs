@@ -0,0 +1,190 @@
+use crate::ctx::Endian;
+use crate::{BytesExt, Error, Result, TryRead};
+use core::cell::Cell;
+use core::convert::TryInto;
+use core::ops::Deref;
+
+/// Default limit of nested pointers followed by a `Base`.
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
+/// The whole buffer that pointer fields are resolved against.
+///
+/// `try_read()` only sees the bytes from the current offset, so a `Base` is passed
+/// down by reference in the context to let nested values follow offsets elsewhere
+/// in the buffer. It also tracks how deeply pointers are nested, so that a cycle of
+/// pointers fails with an error instead of recursing forever.
+#[derive(Debug)]
+pub struct Base<'a> {
+    bytes: &'a [u8],
+    depth: Cell<usize>,
+    max_depth: usize,
+}
+
+impl<'a> Base<'a> {
+    /// Creates a base allowing `DEFAULT_MAX_DEPTH` nested pointers.
+    #[inline]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self::with_max_depth(bytes, DEFAULT_MAX_DEPTH)
+    }
+
+    /// Creates a base allowing `max_depth` nested pointers.
+    #[inline]
+    pub fn with_max_depth(bytes: &'a [u8], max_depth: usize) -> Self {
+        Base {
+            bytes,
+            depth: Cell::new(0),
+            max_depth,
+        }
+    }
+
+    /// Returns the whole buffer.
+    #[inline]
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the position of `bytes`, a subslice of the base buffer, within it.
+    ///
+    /// This is how a `try_read()` implementation finds out where it is, e.g. to
+    /// resolve offsets relative to the start of its own table.
+    #[inline]
+    pub fn position(&self, bytes: &[u8]) -> Result<usize> {
+        let start = self.bytes.as_ptr() as usize;
+        let pos = (bytes.as_ptr() as usize).wrapping_sub(start);
+
+        if pos > self.bytes.len() || bytes.len() > self.bytes.len() - pos {
+            Err(Error::BadInput {
+                err: "Slice is outside the base buffer",
+            })
+        } else {
+            Ok(pos)
+        }
+    }
+}
+
+/// Where a pointer offset is counted from.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Origin {
+    /// The start of the base buffer
+    Base,
+    /// The start of the pointer field itself
+    Relative,
+    /// The given position in the base buffer, e.g. the start of the enclosing table
+    At(usize),
+}
+
+/// Context for `Ptr<T, O>` to read an offset of type `O` and decode the `T` it
+/// points to.
+#[derive(Debug, Copy, Clone)]
+pub struct Follow<'b, 'a, Ctx = ()> {
+    /// The buffer the offset is resolved against
+    pub base: &'b Base<'a>,
+    /// Where the offset is counted from
+    pub origin: Origin,
+    /// Byte order of the offset
+    pub endian: Endian,
+    /// Context of the target value
+    pub ctx: Ctx,
+}
+
+/// A pointer field: an offset of type `O` and the value it points to.
+///
+/// Only the offset counts towards the size read; the target lives elsewhere in
+/// the base buffer. Signed offset types allow negative `Origin::Relative` pointers.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// // An absolute and a self-relative offset to NUL-terminated names
+/// let bytes: &[u8] = b"\x00\x04\x00\x08hello\0world\0";
+/// let base = Base::new(bytes);
+///
+/// let follow = Follow {
+///     base: &base,
+///     origin: Origin::Base,
+///     endian: BE,
+///     ctx: Str::Delimiter(NULL),
+/// };
+/// let relative = Follow { origin: Origin::Relative, ..follow };
+///
+/// let offset = &mut 0;
+/// let first: Ptr<&str, u16> = bytes.read_with(offset, follow).unwrap();
+/// let second: Ptr<&str, i16> = bytes.read_with(offset, relative).unwrap();
+/// assert_eq!((first.offset, *first), (4, "hello"));
+/// assert_eq!((second.offset, *second), (8, "world"));
+/// assert_eq!(*offset, 4);
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Ptr<T, O = u32> {
+    /// The raw offset as stored
+    pub offset: O,
+    /// The value pointed to
+    pub value: T,
+}
+
+impl<T, O> Ptr<T, O> {
+    /// Unwraps the value pointed to.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, O> Deref for Ptr<T, O> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T, O, Ctx> TryRead<'a, Follow<'_, 'a, Ctx>> for Ptr<T, O>
+where
+    T: TryRead<'a, Ctx>,
+    O: TryRead<'a, Endian> + TryInto<i64> + Copy,
+{
+    #[inline]
+    fn try_read(bytes: &'a [u8], follow: Follow<'_, 'a, Ctx>) -> Result<(Self, usize)> {
+        let base = follow.base;
+        let offset = &mut 0;
+        let raw: O = bytes.read_with(offset, follow.endian)?;
+
+        let origin = match follow.origin {
+            Origin::Base => 0,
+            Origin::Relative => base.position(bytes)?,
+            Origin::At(pos) => pos,
+        };
+        let target = raw
+            .try_into()
+            .ok()
+            .and_then(|raw| (origin as i64).checked_add(raw))
+            .and_then(|target| usize::try_from(target).ok())
+            .filter(|&target| target <= base.bytes.len())
+            .ok_or(Error::BadInput {
+                err: "Pointer out of bounds",
+            })?;
+
+        let depth = base.depth.get();
+        if depth >= base.max_depth {
+            return Err(Error::BadInput {
+                err: "Pointers nested too deeply",
+            });
+        }
+
+        base.depth.set(depth + 1);
+        let value = base.bytes.read_with(&mut { target }, follow.ctx);
+        base.depth.set(depth);
+
+        Ok((
+            Ptr {
+                offset: raw,
+                value: value?,
+            },
+            *offset,
+        ))
+    }
+}
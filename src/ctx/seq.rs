@@ -0,0 +1,352 @@
+#![cfg_attr(not(any(feature = "alloc", feature = "heapless")), allow(dead_code))]
+
+use crate::ctx::Endian;
+use crate::{check_len, BytesExt, Error, ReadIntoExt, Result, TryRead, TryReadInto, TryWrite};
+use core::convert::TryFrom;
+use core::ops::DerefMut;
+
+/// Encoding of a count or byte-length prefix.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Prefix {
+    /// Single byte prefix
+    U8,
+    /// 2-byte prefix
+    U16(Endian),
+    /// 4-byte prefix
+    U32(Endian),
+    /// 8-byte prefix
+    U64(Endian),
+}
+
+impl Prefix {
+    /// Returns the number of bytes the prefix takes.
+    #[inline]
+    pub(crate) fn size(self) -> usize {
+        match self {
+            Prefix::U8 => 1,
+            Prefix::U16(_) => 2,
+            Prefix::U32(_) => 4,
+            Prefix::U64(_) => 8,
+        }
+    }
+}
+
+/// Reads a count or byte-length prefix at the offset.
+#[inline]
+pub(crate) fn read_prefix(bytes: &[u8], offset: &mut usize, prefix: Prefix) -> Result<usize> {
+    let len = match prefix {
+        Prefix::U8 => u64::from(bytes.read_with::<u8>(offset, Endian::default())?),
+        Prefix::U16(endian) => u64::from(bytes.read_with::<u16>(offset, endian)?),
+        Prefix::U32(endian) => u64::from(bytes.read_with::<u32>(offset, endian)?),
+        Prefix::U64(endian) => bytes.read_with::<u64>(offset, endian)?,
+    };
+
+    usize::try_from(len).map_err(|_e| Error::BadInput {
+        err: "Prefix overflows usize",
+    })
+}
+
+/// Writes a count or byte-length prefix at the offset.
+#[inline]
+pub(crate) fn write_prefix(
+    bytes: &mut [u8],
+    offset: &mut usize,
+    prefix: Prefix,
+    len: usize,
+) -> Result<()> {
+    let overflow = |_e| Error::BadInput {
+        err: "Length doesn't fit prefix",
+    };
+
+    match prefix {
+        Prefix::U8 => bytes.write_with(
+            offset,
+            u8::try_from(len).map_err(overflow)?,
+            Endian::default(),
+        ),
+        Prefix::U16(endian) => {
+            bytes.write_with(offset, u16::try_from(len).map_err(overflow)?, endian)
+        }
+        Prefix::U32(endian) => {
+            bytes.write_with(offset, u32::try_from(len).map_err(overflow)?, endian)
+        }
+        Prefix::U64(endian) => {
+            bytes.write_with(offset, u64::try_from(len).map_err(overflow)?, endian)
+        }
+    }
+}
+
+/// Determines how many elements a sequence has.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum SeqLen {
+    /// Take a fixed number of elements
+    Count(usize),
+    /// Take elements filling a fixed number of bytes
+    Bytes(usize),
+    /// Take the number of elements read from a prefix
+    CountPrefix(Prefix),
+    /// Take elements filling the number of bytes read from a prefix
+    BytesPrefix(Prefix),
+    /// Take elements until the end of input
+    ToEnd,
+}
+
+/// Context for collections to determine how many elements they have.
+///
+/// `max` restricts the number of elements to prevent allocation blowups on
+/// hostile input; exceeding it is an error. For byte and string collections,
+/// the elements are bytes.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// let bytes: &[u8] = &[0x02, 0x00, 0x01, 0x00, 0x02];
+///
+/// let ctx = Seq {
+///     len: SeqLen::CountPrefix(Prefix::U8),
+///     max: 16,
+///     ctx: BE,
+/// };
+///
+/// let offset = &mut 0;
+/// let list: Vec<u16> = bytes.read_with(offset, ctx).unwrap();
+/// assert_eq!(list, [1, 2]);
+/// assert_eq!(*offset, 5);
+///
+/// let mut write = [0u8; 5];
+/// write.write_with(&mut 0, list, ctx).unwrap();
+/// assert_eq!(write, bytes);
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Seq<Ctx = ()> {
+    /// How the number of elements is determined
+    pub len: SeqLen,
+    /// Maximum number of elements accepted
+    pub max: usize,
+    /// Context of each element
+    pub ctx: Ctx,
+}
+
+/// Reads the elements of a sequence, feeding them to `push`.
+///
+/// Returns the number of bytes consumed, including the prefix.
+#[inline]
+pub(crate) fn read_seq<'a, T, Ctx, F>(bytes: &'a [u8], seq: Seq<Ctx>, mut push: F) -> Result<usize>
+where
+    T: TryRead<'a, Ctx>,
+    Ctx: Clone,
+    F: FnMut(T) -> Result<()>,
+{
+    read_seq_elements(bytes, seq, |bytes, offset, ctx| {
+        push(bytes.read_with(offset, ctx)?)
+    })
+}
+
+/// Reads the elements of a sequence into the existing elements of `items`, so that
+/// their buffers are reused, feeding any further elements to `push`.
+///
+/// Returns the number of elements read, to which the caller truncates `items`, and
+/// the number of bytes consumed, including the prefix.
+#[inline]
+pub(crate) fn read_seq_into<'a, T, Ctx, V, F>(
+    bytes: &'a [u8],
+    seq: Seq<Ctx>,
+    items: &mut V,
+    mut push: F,
+) -> Result<(usize, usize)>
+where
+    T: TryRead<'a, Ctx> + TryReadInto<'a, Ctx>,
+    Ctx: Clone,
+    V: DerefMut<Target = [T]>,
+    F: FnMut(&mut V, T) -> Result<()>,
+{
+    let mut len = 0;
+    let size = read_seq_elements(bytes, seq, |bytes, offset, ctx| {
+        match items.get_mut(len) {
+            Some(item) => bytes.read_into_with(offset, item, ctx)?,
+            None => push(items, bytes.read_with(offset, ctx)?)?,
+        }
+        len += 1;
+        Ok(())
+    })?;
+
+    Ok((len, size))
+}
+
+/// Reads the elements of a sequence with `read`, which reads one element from the
+/// bytes at the offset.
+///
+/// Returns the number of bytes consumed, including the prefix.
+#[inline]
+fn read_seq_elements<'a, Ctx, F>(bytes: &'a [u8], seq: Seq<Ctx>, mut read: F) -> Result<usize>
+where
+    Ctx: Clone,
+    F: FnMut(&'a [u8], &mut usize, Ctx) -> Result<()>,
+{
+    let offset = &mut 0;
+
+    let len = match seq.len {
+        SeqLen::Count(count) => Some(count),
+        SeqLen::CountPrefix(prefix) => Some(read_prefix(bytes, offset, prefix)?),
+        SeqLen::Bytes(_) | SeqLen::BytesPrefix(_) | SeqLen::ToEnd => None,
+    };
+
+    match len {
+        Some(count) => {
+            if count > seq.max {
+                return Err(Error::BadInput {
+                    err: "Too many elements",
+                });
+            }
+            for _ in 0..count {
+                read(bytes, offset, seq.ctx.clone())?;
+            }
+        }
+        None => {
+            let end = match seq.len {
+                SeqLen::Bytes(len) => check_len(bytes, len)?,
+                SeqLen::BytesPrefix(prefix) => {
+                    let len = read_prefix(bytes, offset, prefix)?;
+                    *offset + check_len(&bytes[*offset..], len)?
+                }
+                _ => bytes.len(),
+            };
+            let bytes = &bytes[..end];
+            let mut count = 0;
+            while *offset < end {
+                if count == seq.max {
+                    return Err(Error::BadInput {
+                        err: "Too many elements",
+                    });
+                }
+                let start = *offset;
+                read(bytes, offset, seq.ctx.clone())?;
+                if *offset == start {
+                    return Err(Error::BadInput {
+                        err: "Element consumed no bytes",
+                    });
+                }
+                count += 1;
+            }
+        }
+    }
+
+    Ok(*offset)
+}
+
+/// Writes the `count` elements of a sequence, including the prefix if any.
+///
+/// Returns the number of bytes written.
+#[inline]
+pub(crate) fn write_seq<T, Ctx, I>(
+    bytes: &mut [u8],
+    seq: Seq<Ctx>,
+    count: usize,
+    items: I,
+) -> Result<usize>
+where
+    T: TryWrite<Ctx>,
+    Ctx: Clone,
+    I: IntoIterator<Item = T>,
+{
+    let offset = &mut 0;
+
+    if count > seq.max {
+        return Err(Error::BadInput {
+            err: "Too many elements",
+        });
+    }
+
+    match seq.len {
+        SeqLen::Count(len) if len != count => {
+            return Err(Error::BadInput {
+                err: "Sequence length mismatch",
+            })
+        }
+        SeqLen::CountPrefix(prefix) => write_prefix(bytes, offset, prefix, count)?,
+        SeqLen::BytesPrefix(prefix) => *offset = check_len(bytes, prefix.size())?,
+        _ => (),
+    }
+
+    let start = *offset;
+    for item in items {
+        bytes.write_with(offset, item, seq.ctx.clone())?;
+    }
+
+    match seq.len {
+        SeqLen::Bytes(len) if len != *offset => {
+            return Err(Error::BadInput {
+                err: "Sequence length mismatch",
+            })
+        }
+        SeqLen::BytesPrefix(prefix) => write_prefix(bytes, &mut 0, prefix, *offset - start)?,
+        _ => (),
+    }
+
+    Ok(*offset)
+}
+
+/// Splits the head of `bytes` into a sequence of bytes determined by the context,
+/// returning the content and the number of bytes consumed (including the prefix).
+#[inline]
+pub(crate) fn split_seq<Ctx>(bytes: &[u8], seq: Seq<Ctx>) -> Result<(&[u8], usize)> {
+    let offset = &mut 0;
+
+    let len = match seq.len {
+        SeqLen::Count(len) | SeqLen::Bytes(len) => len,
+        SeqLen::CountPrefix(prefix) | SeqLen::BytesPrefix(prefix) => {
+            read_prefix(bytes, offset, prefix)?
+        }
+        SeqLen::ToEnd => bytes.len(),
+    };
+
+    if len > seq.max {
+        return Err(Error::BadInput {
+            err: "Too many elements",
+        });
+    }
+
+    let content = &bytes[*offset..];
+    let len = check_len(content, len)?;
+
+    Ok((&content[..len], *offset + len))
+}
+
+/// Writes a sequence of bytes, including the prefix if any.
+///
+/// Returns the number of bytes written.
+#[inline]
+pub(crate) fn write_seq_bytes<Ctx>(
+    bytes: &mut [u8],
+    seq: Seq<Ctx>,
+    content: &[u8],
+) -> Result<usize> {
+    let offset = &mut 0;
+
+    if content.len() > seq.max {
+        return Err(Error::BadInput {
+            err: "Too many elements",
+        });
+    }
+
+    match seq.len {
+        SeqLen::Count(len) | SeqLen::Bytes(len) if len != content.len() => {
+            return Err(Error::BadInput {
+                err: "Sequence length mismatch",
+            })
+        }
+        SeqLen::CountPrefix(prefix) | SeqLen::BytesPrefix(prefix) => {
+            write_prefix(bytes, offset, prefix, content.len())?
+        }
+        _ => (),
+    }
+
+    bytes.write_with(offset, content, ())?;
+
+    Ok(*offset)
+}
@@ -1,5 +1,6 @@
+use super::bytes::search;
 use crate::{check_len, Error, Result, TryRead, TryWrite};
-use core::str;
+use core::{slice, str};
 
 /// Context for &str to determine where a &str ends.
 ///
@@ -7,6 +8,10 @@ use core::str;
 ///
 /// Default to `NULL` delimiter.
 ///
+/// Writing an `Encoded` string with a `Str` context produces the layout that the
+/// same context reads back: the delimiter is appended, and strings that contain
+/// the delimiter or don't fit the length are rejected.
+///
 /// # Example
 ///
 /// ```
@@ -26,18 +31,29 @@ use core::str;
 ///
 /// let str: &str = bytes.read_with(&mut 0, Str::DelimiterUntil(NULL, 5)).unwrap();
 /// assert_eq!(str, "hello");
+///
+/// let str: &str = bytes.read_with(&mut 0, Str::Pattern(b", ")).unwrap();
+/// assert_eq!(str, "hello");
+///
+/// let mut write = [0u8; 14];
+/// write.write_with(&mut 0, Encoded("hello, world!"), Str::Delimiter(NULL)).unwrap();
+/// assert_eq!(&write, bytes);
 /// ```
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
-pub enum Str {
+pub enum Str<'p> {
     /// Take fix-length bytes as str
     Len(usize),
     /// Take bytes until reaching a delimiter
     Delimiter(u8),
     /// Take bytes until either delimiter or length reached
     DelimiterUntil(u8, usize),
+    /// Take bytes until reaching a multi-byte delimiter
+    Pattern(&'p [u8]),
+    /// Take bytes until either multi-byte delimiter or length reached
+    PatternUntil(&'p [u8], usize),
 }
 
-impl Default for Str {
+impl Default for Str<'_> {
     #[inline]
     fn default() -> Self {
         Str::Delimiter(NULL)
@@ -52,33 +68,13 @@ pub const SPACE: u8 = 0x20;
 pub const RET: u8 = 0x0a;
 /// Tab string delimiter
 pub const TAB: u8 = 0x09;
+/// Carriage return and line feed string delimiter
+pub const CRLF: &[u8] = b"\r\n";
 
-impl<'a> TryRead<'a, Str> for &'a str {
+impl<'a> TryRead<'a, Str<'_>> for &'a str {
     #[inline]
     fn try_read(bytes: &'a [u8], ctx: Str) -> Result<(Self, usize)> {
-        let (bytes, size) = match ctx {
-            Str::Len(len) => {
-                let len = check_len(bytes, len)?;
-                (&bytes[..len], len)
-            }
-            Str::Delimiter(delimiter) => {
-                let position = bytes
-                    .iter()
-                    .position(|c| *c == delimiter)
-                    .ok_or(Error::Incomplete)?;
-                (&bytes[..position], position + 1)
-            }
-            Str::DelimiterUntil(delimiter, len) => {
-                let position = bytes.iter().take(len).position(|c| *c == delimiter);
-                match position {
-                    Some(position) => (&bytes[..position], position + 1),
-                    None => {
-                        let len = check_len(bytes, len)?;
-                        (&bytes[..len], len)
-                    }
-                }
-            }
-        };
+        let (bytes, size) = split(bytes, ctx)?;
 
         match str::from_utf8(bytes) {
             Ok(str) => Ok((str, size)),
@@ -87,7 +83,7 @@ impl<'a> TryRead<'a, Str> for &'a str {
     }
 }
 
-impl<'a> TryWrite for &'a str {
+impl TryWrite for &str {
     #[inline]
     fn try_write(self, bytes: &mut [u8], _ctx: ()) -> Result<usize> {
         let str_bytes = self.as_bytes();
@@ -99,3 +95,122 @@ impl<'a> TryWrite for &'a str {
         Ok(str_bytes.len())
     }
 }
+
+/// A `&str` to write with a context that delimits or encodes it, such as `Str`,
+/// `Latin1` or `Utf16`.
+///
+/// `&str` itself is only written as is with the `()` context, so that `write()`
+/// can infer it.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Encoded<'s>(pub &'s str);
+
+impl TryWrite<Str<'_>> for Encoded<'_> {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Str) -> Result<usize> {
+        terminate(bytes, self.0.bytes(), self.0.len(), ctx)
+    }
+}
+
+/// Splits the head of `bytes` into the string content determined by the context,
+/// returning the content and the number of bytes consumed (including the delimiter).
+#[inline]
+pub(crate) fn split<'a>(bytes: &'a [u8], ctx: Str) -> Result<(&'a [u8], usize)> {
+    let (pattern, limit) = match ctx {
+        Str::Len(len) => {
+            let len = check_len(bytes, len)?;
+            return Ok((&bytes[..len], len));
+        }
+        Str::Delimiter(ref delimiter) => (slice::from_ref(delimiter), None),
+        Str::DelimiterUntil(ref delimiter, len) => (slice::from_ref(delimiter), Some(len)),
+        Str::Pattern(pattern) => (pattern, None),
+        Str::PatternUntil(pattern, len) => (pattern, Some(len)),
+    };
+
+    match search(bytes, pattern, limit)? {
+        Some(position) => Ok((&bytes[..position], position + pattern.len())),
+        None => {
+            let len = limit.unwrap_or(bytes.len());
+            Ok((&bytes[..len], len))
+        }
+    }
+}
+
+/// Writes the `len` bytes of string content yielded by `content`, and appends the
+/// delimiter if the context requires one.
+///
+/// Everything is validated before writing, so an error leaves `bytes` untouched.
+/// Returns the total number of bytes written, so that reading back with the same
+/// context yields the same string.
+#[inline]
+pub(crate) fn terminate<I>(bytes: &mut [u8], content: I, len: usize, ctx: Str) -> Result<usize>
+where
+    I: Iterator<Item = u8> + Clone,
+{
+    let (pattern, append) = match ctx {
+        Str::Len(expected) => {
+            if len != expected {
+                return Err(Error::BadInput {
+                    err: "Str length mismatch",
+                });
+            }
+            (&[][..], false)
+        }
+        Str::Delimiter(ref delimiter) => (slice::from_ref(delimiter), true),
+        Str::DelimiterUntil(ref delimiter, max) => {
+            (slice::from_ref(delimiter), delimit(len, 1, max)?)
+        }
+        Str::Pattern(pattern) => (pattern, true),
+        Str::PatternUntil(pattern, max) => (pattern, delimit(len, pattern.len(), max)?),
+    };
+
+    if !pattern.is_empty() {
+        // The first occurrence must be the appended delimiter; this also catches a
+        // content suffix that forms the delimiter together with the head of it.
+        let tail = if append { pattern } else { &[] };
+        let mut rest = content.clone();
+        for _ in 0..len {
+            let mut window = rest.clone().chain(tail.iter().copied());
+            if pattern.iter().all(|&b| window.next() == Some(b)) {
+                return Err(Error::BadInput {
+                    err: "Str contains delimiter",
+                });
+            }
+            rest.next();
+        }
+    } else if append {
+        return Err(Error::BadInput {
+            err: "Pattern is empty",
+        });
+    }
+
+    let size = check_len(bytes, if append { len + pattern.len() } else { len })?;
+    for (dst, src) in bytes.iter_mut().zip(content.take(len)) {
+        *dst = src;
+    }
+    if append {
+        bytes[len..size].copy_from_slice(pattern);
+    }
+
+    Ok(size)
+}
+
+/// Checks `len` bytes of content against the restricted length `max`, returning
+/// whether there is room to append a delimiter of `pattern_len` bytes.
+///
+/// Without room, the content must fill up the length.
+#[inline]
+fn delimit(len: usize, pattern_len: usize, max: usize) -> Result<bool> {
+    if len > max {
+        Err(Error::BadInput {
+            err: "Str is longer than restricted length",
+        })
+    } else if len + pattern_len <= max {
+        Ok(true)
+    } else if len == max {
+        Ok(false)
+    } else {
+        Err(Error::BadInput {
+            err: "Pattern doesn't fit restricted length",
+        })
+    }
+}
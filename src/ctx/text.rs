@@ -0,0 +1,320 @@
+use super::str::{split, terminate};
+use crate::ctx::{Encoded, Str};
+use crate::{Error, Result, TryRead, TryWrite};
+use core::ops::Deref;
+use core::{char, fmt, str};
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+
+/// Context for `AsciiStr` that only accepts 7-bit ASCII text.
+///
+/// The wrapped `Str` determines where the string ends.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// let bytes: &[u8] = b"hello\0w\xf6rld\0";
+///
+/// let offset = &mut 0;
+/// let str: AsciiStr = bytes.read_with(offset, Ascii(Str::Delimiter(NULL))).unwrap();
+/// assert_eq!(str.as_str(), "hello");
+/// assert!(bytes.read_with::<AsciiStr>(offset, Ascii(Str::Delimiter(NULL))).is_err());
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct Ascii<'p>(pub Str<'p>);
+
+/// Context for `Latin1Str` to decode ISO 8859-1 text, where every byte is a `char`.
+///
+/// The wrapped `Str` determines where the string ends.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// let bytes: &[u8] = b"w\xf6rld\0";
+///
+/// let str: Latin1Str = bytes.read_with(&mut 0, Latin1(Str::Delimiter(NULL))).unwrap();
+/// assert_eq!(str, "wörld");
+///
+/// let mut write = [0u8; 6];
+/// write.write_with(&mut 0, Encoded("wörld"), Latin1(Str::Delimiter(NULL))).unwrap();
+/// assert_eq!(write, bytes);
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct Latin1<'p>(pub Str<'p>);
+
+/// Context for `LossyStr` to decode UTF-8 text, replacing invalid sequences
+/// with `U+FFFD REPLACEMENT CHARACTER` instead of failing.
+///
+/// The wrapped `Str` determines where the string ends.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// let bytes: &[u8] = b"w\xf6rld\0";
+///
+/// let str: LossyStr = bytes.read_with(&mut 0, Lossy(Str::Delimiter(NULL))).unwrap();
+/// assert_eq!(str, "w\u{fffd}rld");
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct Lossy<'p>(pub Str<'p>);
+
+/// A zero-copy 7-bit ASCII string borrowed from a byte slice.
+///
+/// Dereferences to `&str`, since ASCII is a subset of UTF-8.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct AsciiStr<'a> {
+    str: &'a str,
+}
+
+impl<'a> AsciiStr<'a> {
+    /// Returns the content as `&str`.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.str
+    }
+}
+
+impl Deref for AsciiStr<'_> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.str
+    }
+}
+
+impl PartialEq<str> for AsciiStr<'_> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.str == other
+    }
+}
+
+impl PartialEq<&str> for AsciiStr<'_> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.str == *other
+    }
+}
+
+impl fmt::Display for AsciiStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.str)
+    }
+}
+
+/// A zero-copy ISO 8859-1 (Latin-1) string borrowed from a byte slice.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Latin1Str<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Latin1Str<'a> {
+    /// Returns the number of bytes, which is also the number of `char`s.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns `true` if the string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns the encoded bytes, excluding the delimiter.
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns an iterator decoding the `char`s of the string.
+    #[inline]
+    pub fn chars(&self) -> impl Iterator<Item = char> + 'a {
+        self.bytes.iter().map(|&b| char::from(b))
+    }
+}
+
+impl PartialEq<str> for Latin1Str<'_> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.chars().eq(other.chars())
+    }
+}
+
+impl PartialEq<&str> for Latin1Str<'_> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl fmt::Display for Latin1Str<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use core::fmt::Write;
+
+        self.chars().try_for_each(|c| f.write_char(c))
+    }
+}
+
+/// A zero-copy, possibly invalid UTF-8 string borrowed from a byte slice.
+///
+/// Invalid sequences are replaced by `U+FFFD REPLACEMENT CHARACTER` when decoded
+/// by `chars()`, the same way as `String::from_utf8_lossy()`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct LossyStr<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> LossyStr<'a> {
+    /// Returns the number of bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns `true` if the string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns the raw bytes, excluding the delimiter.
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the content as `&str` if it's valid UTF-8.
+    #[inline]
+    pub fn as_str(&self) -> Option<&'a str> {
+        str::from_utf8(self.bytes).ok()
+    }
+
+    /// Returns an iterator decoding the `char`s of the string.
+    #[inline]
+    pub fn chars(&self) -> impl Iterator<Item = char> + 'a {
+        self.bytes.utf8_chunks().flat_map(|chunk| {
+            let replacement = if chunk.invalid().is_empty() {
+                None
+            } else {
+                Some(char::REPLACEMENT_CHARACTER)
+            };
+            chunk.valid().chars().chain(replacement)
+        })
+    }
+}
+
+impl PartialEq<str> for LossyStr<'_> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.chars().eq(other.chars())
+    }
+}
+
+impl PartialEq<&str> for LossyStr<'_> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl fmt::Display for LossyStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use core::fmt::Write;
+
+        self.chars().try_for_each(|c| f.write_char(c))
+    }
+}
+
+impl<'a> TryRead<'a, Ascii<'_>> for AsciiStr<'a> {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Ascii) -> Result<(Self, usize)> {
+        let (bytes, size) = split(bytes, ctx.0)?;
+
+        match str::from_utf8(bytes) {
+            Ok(str) if str.is_ascii() => Ok((AsciiStr { str }, size)),
+            _ => Err(Error::BadInput { err: "ASCII Error" }),
+        }
+    }
+}
+
+impl TryWrite<Ascii<'_>> for Encoded<'_> {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Ascii) -> Result<usize> {
+        if !self.0.is_ascii() {
+            return Err(Error::BadInput { err: "ASCII Error" });
+        }
+
+        TryWrite::try_write(self, bytes, ctx.0)
+    }
+}
+
+impl<'a> TryRead<'a, Latin1<'_>> for Latin1Str<'a> {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Latin1) -> Result<(Self, usize)> {
+        let (bytes, size) = split(bytes, ctx.0)?;
+
+        Ok((Latin1Str { bytes }, size))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> TryRead<'a, Latin1<'_>> for String {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Latin1) -> Result<(Self, usize)> {
+        let (str, size) = <Latin1Str as TryRead<Latin1>>::try_read(bytes, ctx)?;
+
+        Ok((str.chars().collect(), size))
+    }
+}
+
+impl TryWrite<Latin1<'_>> for Encoded<'_> {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Latin1) -> Result<usize> {
+        if self.0.chars().any(|c| u32::from(c) > 0xff) {
+            return Err(Error::BadInput {
+                err: "Latin-1 Error",
+            });
+        }
+
+        let content = self.0.chars().map(|c| c as u8);
+        terminate(bytes, content, self.0.chars().count(), ctx.0)
+    }
+}
+
+impl<'a> TryRead<'a, Lossy<'_>> for LossyStr<'a> {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Lossy) -> Result<(Self, usize)> {
+        let (bytes, size) = split(bytes, ctx.0)?;
+
+        Ok((LossyStr { bytes }, size))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> TryRead<'a, Lossy<'_>> for Cow<'a, str> {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Lossy) -> Result<(Self, usize)> {
+        let (bytes, size) = split(bytes, ctx.0)?;
+
+        Ok((String::from_utf8_lossy(bytes), size))
+    }
+}
+
+impl TryWrite<Lossy<'_>> for Encoded<'_> {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Lossy) -> Result<usize> {
+        TryWrite::try_write(self, bytes, ctx.0)
+    }
+}
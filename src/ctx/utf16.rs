@@ -0,0 +1,231 @@
+use crate::ctx::{Encoded, Endian};
+use crate::{check_len, BytesExt, Error, Result, TryRead, TryWrite};
+use core::{char, fmt};
+
+/// Context for `Utf16Str` to determine where a UTF-16 string ends.
+///
+/// Lengths are counted in 16-bit code units, not bytes.
+/// The NUL terminator will **not** be included in the result.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// let bytes: &[u8] = &[0x68, 0x00, 0x69, 0x00, 0x00, 0x00];
+///
+/// let str: Utf16Str = bytes.read_with(&mut 0, Utf16::Len(2, LE)).unwrap();
+/// assert_eq!(str, "hi");
+///
+/// let offset = &mut 0;
+/// let str: Utf16Str = bytes.read_with(offset, Utf16::Nul(LE)).unwrap();
+/// assert_eq!(str, "hi");
+/// assert_eq!(*offset, 6);
+///
+/// let str: Utf16Str = bytes.read_with(&mut 0, Utf16::NulUntil(1, LE)).unwrap();
+/// assert_eq!(str, "h");
+///
+/// let mut write = [0u8; 6];
+/// write.write_with(&mut 0, Encoded("hi"), Utf16::Nul(LE)).unwrap();
+/// assert_eq!(write, bytes);
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Utf16 {
+    /// Take fix number of code units
+    Len(usize, Endian),
+    /// Take code units until reaching a NUL code unit
+    Nul(Endian),
+    /// Take code units until either a NUL code unit or the number of code units reached
+    NulUntil(usize, Endian),
+}
+
+/// A zero-copy UTF-16 string borrowed from a byte slice.
+///
+/// The content is validated on read, including surrogate pairs, and decoded lazily
+/// by `chars()`.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// let bytes: &[u8] = &[0xd8, 0x3d, 0xde, 0x00, 0x00, 0x21];
+///
+/// let str: Utf16Str = bytes.read_with(&mut 0, Utf16::Len(3, BE)).unwrap();
+/// assert_eq!(str.len(), 3);
+/// assert_eq!(str.chars().collect::<Vec<_>>(), ['\u{1f600}', '!']);
+///
+/// // Unpaired surrogate
+/// let bytes: &[u8] = &[0xd8, 0x3d, 0x00, 0x21];
+/// assert!(bytes.read_with::<Utf16Str>(&mut 0, Utf16::Len(2, BE)).is_err());
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Utf16Str<'a> {
+    bytes: &'a [u8],
+    endian: Endian,
+}
+
+impl<'a> Utf16Str<'a> {
+    /// Returns the number of 16-bit code units.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len() / 2
+    }
+
+    /// Returns `true` if the string has no code units.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns the encoded bytes, excluding the NUL terminator.
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the byte order of the code units.
+    #[inline]
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    /// Returns an iterator over the 16-bit code units.
+    #[inline]
+    pub fn units(&self) -> impl Iterator<Item = u16> + 'a {
+        let endian = self.endian;
+        self.bytes.chunks_exact(2).map(move |unit| match endian {
+            Endian::Big => u16::from_be_bytes([unit[0], unit[1]]),
+            Endian::Little => u16::from_le_bytes([unit[0], unit[1]]),
+        })
+    }
+
+    /// Returns an iterator decoding the `char`s of the string.
+    #[inline]
+    pub fn chars(&self) -> impl Iterator<Item = char> + 'a {
+        // Surrogate pairs are validated on read, so no replacement will happen.
+        char::decode_utf16(self.units()).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+}
+
+impl<'a, 'b> PartialEq<Utf16Str<'b>> for Utf16Str<'a> {
+    #[inline]
+    fn eq(&self, other: &Utf16Str<'b>) -> bool {
+        self.units().eq(other.units())
+    }
+}
+
+impl Eq for Utf16Str<'_> {}
+
+impl PartialEq<str> for Utf16Str<'_> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.units().eq(other.encode_utf16())
+    }
+}
+
+impl PartialEq<&str> for Utf16Str<'_> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl fmt::Display for Utf16Str<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use core::fmt::Write;
+
+        self.chars().try_for_each(|c| f.write_char(c))
+    }
+}
+
+impl<'a> TryRead<'a, Utf16> for Utf16Str<'a> {
+    #[inline]
+    fn try_read(bytes: &'a [u8], ctx: Utf16) -> Result<(Self, usize)> {
+        let nul = |unit: &[u8]| unit == [0, 0];
+
+        let (content, size, endian) = match ctx {
+            Utf16::Len(len, endian) => {
+                let len = check_len(bytes, len.saturating_mul(2))?;
+                (&bytes[..len], len, endian)
+            }
+            Utf16::Nul(endian) => {
+                let position = bytes
+                    .chunks_exact(2)
+                    .position(nul)
+                    .ok_or(Error::Incomplete)?;
+                (&bytes[..position * 2], position * 2 + 2, endian)
+            }
+            Utf16::NulUntil(len, endian) => {
+                let position = bytes.chunks_exact(2).take(len).position(nul);
+                match position {
+                    Some(position) => (&bytes[..position * 2], position * 2 + 2, endian),
+                    None => {
+                        let len = check_len(bytes, len.saturating_mul(2))?;
+                        (&bytes[..len], len, endian)
+                    }
+                }
+            }
+        };
+
+        let str = Utf16Str {
+            bytes: content,
+            endian,
+        };
+
+        if char::decode_utf16(str.units()).any(|c| c.is_err()) {
+            return Err(Error::BadInput { err: "UTF16 Error" });
+        }
+
+        Ok((str, size))
+    }
+}
+
+impl TryWrite<Utf16> for Encoded<'_> {
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Utf16) -> Result<usize> {
+        let len = self.0.encode_utf16().count();
+
+        let (endian, nul) = match ctx {
+            Utf16::Len(expected, endian) => {
+                if len != expected {
+                    return Err(Error::BadInput {
+                        err: "Str length mismatch",
+                    });
+                }
+                (endian, false)
+            }
+            Utf16::Nul(endian) => (endian, true),
+            Utf16::NulUntil(max, endian) => {
+                if len > max {
+                    return Err(Error::BadInput {
+                        err: "Str is longer than restricted length",
+                    });
+                }
+                (endian, len < max)
+            }
+        };
+
+        if let Utf16::Nul(_) | Utf16::NulUntil(..) = ctx {
+            if self.0.contains('\0') {
+                return Err(Error::BadInput {
+                    err: "Str contains delimiter",
+                });
+            }
+        }
+
+        check_len(bytes, (len + usize::from(nul)) * 2)?;
+
+        let offset = &mut 0;
+        for unit in self.0.encode_utf16() {
+            bytes.write_with(offset, unit, endian)?;
+        }
+        if nul {
+            bytes.write_with(offset, 0u16, endian)?;
+        }
+
+        Ok(*offset)
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{Error, Result, TryRead, TryWrite};
+use core::ops::{Deref, Range, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive};
+
+/// Context for `Validated<T>` to check a value after reading and before writing.
+///
+/// The first field is the context of `T`, and the second is the check, which can be
+/// a range, a [`Predicate`](struct.Predicate.html), a [`Reserved`](struct.Reserved.html)
+/// mask, or a tuple of checks that must all pass. Invalid values are rejected with
+/// `Error::Invalid`.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// let bytes: &[u8] = &[50, 150, 0x05, 0xdc, 0x80];
+///
+/// let offset = &mut 0;
+/// let percent: Validated<u8> = bytes.read_with(offset, Validate(BE, 0..=100)).unwrap();
+/// assert_eq!(*percent, 50);
+/// assert_eq!(
+///     bytes.read_with::<Validated<u8>>(offset, Validate(BE, 0..=100)),
+///     Err(Error::Invalid { err: "Value is out of range" })
+/// );
+/// assert_eq!(*offset, 1);
+///
+/// let mtu = Predicate(|len: &u16| *len <= 1500, "Length exceeds MTU");
+/// let len: Validated<u16> = bytes.read_with(&mut 2, Validate(BE, mtu)).unwrap();
+/// assert_eq!(len.into_inner(), 1500);
+///
+/// let flags = Validate(BE, Reserved(0x7f_u8));
+/// assert!(bytes.read_with::<Validated<u8>>(&mut 4, flags).is_ok());
+/// assert!(bytes.read_with::<Validated<u8>>(&mut 2, flags).is_err());
+///
+/// let mut write = [0u8; 1];
+/// assert!(write.write_with(&mut 0, Validated(101u8), Validate(BE, 0..=100)).is_err());
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Validate<Ctx, C>(pub Ctx, pub C);
+
+/// A value read or written with the [`Validate`](struct.Validate.html) context.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Default)]
+pub struct Validated<T>(pub T);
+
+impl<T> Validated<T> {
+    /// Unwraps the value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Validated<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<'a, T, Ctx, C> TryRead<'a, Validate<Ctx, C>> for Validated<T>
+where
+    T: TryRead<'a, Ctx>,
+    C: Check<T>,
+{
+    #[inline]
+    fn try_read(bytes: &'a [u8], Validate(ctx, check): Validate<Ctx, C>) -> Result<(Self, usize)> {
+        let (value, size) = T::try_read(bytes, ctx)?;
+        check.check(&value).map_err(|err| Error::Invalid { err })?;
+
+        Ok((Validated(value), size))
+    }
+}
+
+impl<T, Ctx, C> TryWrite<Validate<Ctx, C>> for Validated<T>
+where
+    T: TryWrite<Ctx>,
+    C: Check<T>,
+{
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], Validate(ctx, check): Validate<Ctx, C>) -> Result<usize> {
+        check.check(&self.0).map_err(|err| Error::Invalid { err })?;
+
+        self.0.try_write(bytes, ctx)
+    }
+}
+
+/// A validation applied by the [`Validate`](struct.Validate.html) context.
+pub trait Check<T: ?Sized> {
+    /// Returns `Err` with the reason if the value is invalid.
+    fn check(&self, value: &T) -> core::result::Result<(), &'static str>;
+}
+
+macro_rules! range_impl {
+    ($range: ty) => {
+        impl<T: PartialOrd> Check<T> for $range {
+            #[inline]
+            fn check(&self, value: &T) -> core::result::Result<(), &'static str> {
+                if self.contains(value) {
+                    Ok(())
+                } else {
+                    Err("Value is out of range")
+                }
+            }
+        }
+    };
+}
+
+range_impl!(Range<T>);
+range_impl!(RangeInclusive<T>);
+range_impl!(RangeFrom<T>);
+range_impl!(RangeTo<T>);
+range_impl!(RangeToInclusive<T>);
+
+/// Check passing if the predicate returns `true`, failing with the given reason
+/// otherwise.
+#[derive(Debug, Copy, Clone)]
+pub struct Predicate<F>(pub F, pub &'static str);
+
+impl<T: ?Sized, F> Check<T> for Predicate<F>
+where
+    F: Fn(&T) -> bool,
+{
+    #[inline]
+    fn check(&self, value: &T) -> core::result::Result<(), &'static str> {
+        if (self.0)(value) {
+            Ok(())
+        } else {
+            Err(self.1)
+        }
+    }
+}
+
+/// Check passing if none of the reserved bits in the mask are set.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Reserved<T>(pub T);
+
+macro_rules! reserved_impl {
+    ($ty: ty) => {
+        impl Check<$ty> for Reserved<$ty> {
+            #[inline]
+            fn check(&self, value: &$ty) -> core::result::Result<(), &'static str> {
+                if value & self.0 == 0 {
+                    Ok(())
+                } else {
+                    Err("Reserved bits are set")
+                }
+            }
+        }
+    };
+}
+
+reserved_impl!(u8);
+reserved_impl!(u16);
+reserved_impl!(u32);
+reserved_impl!(u64);
+reserved_impl!(usize);
+
+impl<T: ?Sized, A, B> Check<T> for (A, B)
+where
+    A: Check<T>,
+    B: Check<T>,
+{
+    #[inline]
+    fn check(&self, value: &T) -> core::result::Result<(), &'static str> {
+        self.0.check(value)?;
+        self.1.check(value)
+    }
+}
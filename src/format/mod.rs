@@ -0,0 +1,175 @@
+//! A compact binary serde data format backed by the byte contexts.
+//!
+//! Numbers are written with the configured `Endian`; strings, byte arrays, sequences
+//! and maps are preceded by their length, and enum variants by their index, both
+//! encoded as configured by `Length`. Structs, tuples and options are written inline,
+//! with a `bool` flag in front of an option.
+//!
+//! The format is not self-describing, so `deserialize_any` is not supported.
+//! Deserializing `&str` and `&[u8]` borrows from the input without copying.
+//!
+//! Requires the `serde` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use byte::*;
+//! use byte::format::{from_bytes, to_bytes, Config, Length};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Header<'a> {
+//!     name: &'a str,
+//!     id: u16,
+//!     enabled: bool,
+//! }
+//!
+//! let config = Config {
+//!     endian: BE,
+//!     len: Length::Varint,
+//! };
+//!
+//! let header = Header {
+//!     name: "hello",
+//!     id: 0x1234,
+//!     enabled: true,
+//! };
+//!
+//! let mut bytes = [0u8; 16];
+//! let len = to_bytes(&header, &mut bytes, config).unwrap();
+//! assert_eq!(&bytes[..len], b"\x05hello\x12\x34\xff");
+//!
+//! let (read, size): (Header, usize) = from_bytes(&bytes, config).unwrap();
+//! assert_eq!(read, header);
+//! assert_eq!(size, len);
+//! ```
+
+mod de;
+mod ser;
+
+pub use self::de::Deserializer;
+pub use self::ser::Serializer;
+
+use crate::ctx::{read_prefix, write_prefix, Endian, Prefix, LE};
+use crate::{BytesExt, Error, Result};
+use core::convert::TryFrom;
+use core::fmt::Display;
+use serde::{Deserialize, Serialize};
+
+/// Configuration of the data format.
+///
+/// Default to little endian numbers with 4-byte little endian lengths.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Config {
+    /// Byte order of numbers
+    pub endian: Endian,
+    /// Encoding of lengths and enum variant indices
+    pub len: Length,
+}
+
+impl Default for Config {
+    #[inline]
+    fn default() -> Self {
+        Config {
+            endian: LE,
+            len: Length::Fixed(Prefix::U32(LE)),
+        }
+    }
+}
+
+/// Encoding of lengths and enum variant indices.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Length {
+    /// Fixed-size integer
+    Fixed(Prefix),
+    /// LEB128 variable-length integer, taking 7 bits per byte
+    Varint,
+}
+
+/// Serializes a value into the head of the bytes, returning the number of bytes written.
+#[inline]
+pub fn to_bytes<T>(value: &T, bytes: &mut [u8], config: Config) -> Result<usize>
+where
+    T: Serialize + ?Sized,
+{
+    let mut serializer = Serializer::new(bytes, config);
+    value.serialize(&mut serializer)?;
+
+    Ok(serializer.offset())
+}
+
+/// Deserializes a value from the head of the bytes, returning the value and the number
+/// of bytes consumed.
+#[inline]
+pub fn from_bytes<'de, T>(bytes: &'de [u8], config: Config) -> Result<(T, usize)>
+where
+    T: Deserialize<'de>,
+{
+    let mut deserializer = Deserializer::new(bytes, config);
+    let value = T::deserialize(&mut deserializer)?;
+
+    Ok((value, deserializer.offset()))
+}
+
+/// Reads a length or enum variant index at the offset.
+#[inline]
+fn read_len(bytes: &[u8], offset: &mut usize, len: Length) -> Result<usize> {
+    match len {
+        Length::Fixed(prefix) => read_prefix(bytes, offset, prefix),
+        Length::Varint => {
+            let mut value = 0u64;
+            for shift in (0..64).step_by(7) {
+                let byte: u8 = bytes.read(offset)?;
+                let bits = u64::from(byte & 0x7f);
+                if shift == 63 && bits > 1 {
+                    break;
+                }
+                value |= bits << shift;
+                if byte & 0x80 == 0 {
+                    return usize::try_from(value).map_err(|_e| Error::BadInput {
+                        err: "Prefix overflows usize",
+                    });
+                }
+            }
+            Err(Error::BadInput {
+                err: "Varint overflows u64",
+            })
+        }
+    }
+}
+
+/// Writes a length or enum variant index at the offset.
+#[inline]
+fn write_len(bytes: &mut [u8], offset: &mut usize, len: Length, value: usize) -> Result<()> {
+    match len {
+        Length::Fixed(prefix) => write_prefix(bytes, offset, prefix, value),
+        Length::Varint => {
+            let mut value = value as u64;
+            while value >= 0x80 {
+                bytes.write(offset, (value as u8) | 0x80)?;
+                value >>= 7;
+            }
+            bytes.write(offset, value as u8)
+        }
+    }
+}
+
+impl serde::ser::Error for Error {
+    /// The message is discarded, as `Error` carries only static messages.
+    #[inline]
+    fn custom<T: Display>(_msg: T) -> Self {
+        Error::BadInput {
+            err: "Serialize Error",
+        }
+    }
+}
+
+impl serde::de::Error for Error {
+    /// The message is discarded, as `Error` carries only static messages.
+    #[inline]
+    fn custom<T: Display>(_msg: T) -> Self {
+        Error::BadInput {
+            err: "Deserialize Error",
+        }
+    }
+}
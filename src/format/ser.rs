@@ -0,0 +1,346 @@
+use super::{write_len, Config};
+use crate::{check_len, BytesExt, Error, Result};
+use core::fmt::{self, Display, Write};
+use serde::ser::{self, Serialize};
+
+/// Serializer writing the data format into a byte slice.
+///
+/// See the [module documentation](index.html) for the format.
+pub struct Serializer<'a> {
+    bytes: &'a mut [u8],
+    offset: usize,
+    config: Config,
+}
+
+impl<'a> Serializer<'a> {
+    /// Creates a serializer writing into the head of the bytes.
+    #[inline]
+    pub fn new(bytes: &'a mut [u8], config: Config) -> Self {
+        Serializer {
+            bytes,
+            offset: 0,
+            config,
+        }
+    }
+
+    /// Returns the number of bytes written so far.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    #[inline]
+    fn write_len(&mut self, len: usize) -> Result<()> {
+        write_len(self.bytes, &mut self.offset, self.config.len, len)
+    }
+
+    #[inline]
+    fn write_variant(&mut self, variant_index: u32) -> Result<()> {
+        self.write_len(variant_index as usize)
+    }
+
+    #[inline]
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.write_len(bytes.len())?;
+        self.bytes.write_with(&mut self.offset, bytes, ())
+    }
+}
+
+macro_rules! serialize_num {
+    ($method: ident, $ty: ty) => {
+        #[inline]
+        fn $method(self, v: $ty) -> Result<()> {
+            self.bytes
+                .write_with(&mut self.offset, v, self.config.endian)
+        }
+    };
+}
+
+impl ser::Serializer for &mut Serializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    #[inline]
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.bytes.write(&mut self.offset, v)
+    }
+
+    serialize_num!(serialize_i8, i8);
+    serialize_num!(serialize_i16, i16);
+    serialize_num!(serialize_i32, i32);
+    serialize_num!(serialize_i64, i64);
+    serialize_num!(serialize_u8, u8);
+    serialize_num!(serialize_u16, u16);
+    serialize_num!(serialize_u32, u32);
+    serialize_num!(serialize_u64, u64);
+    serialize_num!(serialize_f32, f32);
+    serialize_num!(serialize_f64, f64);
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.serialize_u32(v as u32)
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.write_bytes(v.as_bytes())
+    }
+
+    #[inline]
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.write_bytes(v)
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<()> {
+        self.serialize_bool(false)
+    }
+
+    #[inline]
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        self.serialize_bool(true)?;
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<()> {
+        self.write_variant(variant_index)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.write_variant(variant_index)?;
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self> {
+        let len = len.ok_or(Error::BadInput {
+            err: "Sequence length is unknown",
+        })?;
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn serialize_tuple(self, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.write_variant(variant_index)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn serialize_map(self, len: Option<usize>) -> Result<Self> {
+        self.serialize_seq(len)
+    }
+
+    #[inline]
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.write_variant(variant_index)?;
+        Ok(self)
+    }
+
+    /// Formats the value twice: once to find its length, then into the bytes.
+    fn collect_str<T: Display + ?Sized>(self, value: &T) -> Result<()> {
+        let mut counter = Counter(0);
+        write!(counter, "{}", value).map_err(|_e| Error::BadInput {
+            err: "Serialize Error",
+        })?;
+        self.write_len(counter.0)?;
+
+        let bytes = &mut self.bytes[self.offset..];
+        let len = check_len(bytes, counter.0)?;
+        let mut cursor = Cursor {
+            bytes: &mut bytes[..len],
+            offset: 0,
+        };
+        write!(cursor, "{}", value).map_err(|_e| Error::BadInput {
+            err: "Serialize Error",
+        })?;
+        if cursor.offset != len {
+            return Err(Error::BadInput {
+                err: "Serialize Error",
+            });
+        }
+        self.offset += len;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Counts the length of formatted output.
+struct Counter(usize);
+
+impl Write for Counter {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+/// Writes formatted output into a byte slice.
+struct Cursor<'a> {
+    bytes: &'a mut [u8],
+    offset: usize,
+}
+
+impl Write for Cursor<'_> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.bytes
+            .write_with(&mut self.offset, s.as_bytes(), ())
+            .map_err(|_e| fmt::Error)
+    }
+}
+
+macro_rules! serialize_compound {
+    ($trait: ident, $method: ident) => {
+        impl ser::$trait for &mut Serializer<'_> {
+            type Ok = ();
+            type Error = Error;
+
+            #[inline]
+            fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+                value.serialize(&mut **self)
+            }
+
+            #[inline]
+            fn end(self) -> Result<()> {
+                Ok(())
+            }
+        }
+    };
+}
+
+serialize_compound!(SerializeSeq, serialize_element);
+serialize_compound!(SerializeTuple, serialize_element);
+serialize_compound!(SerializeTupleStruct, serialize_field);
+serialize_compound!(SerializeTupleVariant, serialize_field);
+
+impl ser::SerializeMap for &mut Serializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        key.serialize(&mut **self)
+    }
+
+    #[inline]
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for &mut Serializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for &mut Serializer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{BytesExt, Error, Result, TryWrite};
+use core::mem;
+
+/// An output that values can be written into, either by copying them or, for byte
+/// slices, by referencing them.
+///
+/// Implemented by [`SliceWriter`](struct.SliceWriter.html), which copies everything
+/// into a byte slice, and [`GatherWriter`](struct.GatherWriter.html), which produces a
+/// gather list.
+pub trait Sink<'a> {
+    /// Writes a value using the default context.
+    #[inline]
+    fn put<T, Ctx>(&mut self, t: T) -> Result<()>
+    where
+        T: TryWrite<Ctx>,
+        Ctx: Default,
+    {
+        self.put_with(t, Default::default())
+    }
+
+    /// Writes a value specifying the context.
+    fn put_with<T, Ctx>(&mut self, t: T, ctx: Ctx) -> Result<()>
+    where
+        T: TryWrite<Ctx>;
+
+    /// Writes bytes, which the output may reference instead of copying.
+    fn put_bytes(&mut self, bytes: &'a [u8]) -> Result<()>;
+
+    /// Returns the number of bytes written so far.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if no bytes have been written.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A data structure that can be written into any [`Sink`](trait.Sink.html).
+///
+/// Implement this instead of `TryWrite` to let large byte fields be referenced by
+/// vectored output; the same impl then drives copying output through `SliceWriter`.
+/// Existing `TryWrite` types can be written into a sink through [`Copied`](struct.Copied.html).
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+///
+/// struct Packet<'a> {
+///     id: u16,
+///     payload: &'a [u8],
+/// }
+///
+/// impl<'a> TryWriteTo<'a> for Packet<'a> {
+///     fn try_write_to<S: Sink<'a>>(self, sink: &mut S, _ctx: ()) -> Result<()> {
+///         sink.put_with(self.id, BE)?;
+///         sink.put_with(self.payload.len() as u16, BE)?;
+///         sink.put_bytes(self.payload)
+///     }
+/// }
+///
+/// let payload = [0xaa; 128];
+///
+/// // Copying output
+/// let mut bytes = [0u8; 132];
+/// let mut writer = SliceWriter::new(&mut bytes);
+/// Packet { id: 1, payload: &payload }.try_write_to(&mut writer, ()).unwrap();
+/// assert_eq!(writer.len(), 132);
+/// assert_eq!(bytes[..4], [0x00, 0x01, 0x00, 0x80]);
+///
+/// // Vectored output, referencing the payload
+/// let mut segments = [&[][..]; 4];
+/// let mut scratch = [0u8; 16];
+/// let mut writer = GatherWriter::new(&mut segments, &mut scratch, 64);
+/// Packet { id: 1, payload: &payload }.try_write_to(&mut writer, ()).unwrap();
+/// let segments = writer.finish().unwrap();
+/// assert_eq!(segments, [&bytes[..4], &payload[..]]);
+/// assert_eq!(segments[1].as_ptr(), payload.as_ptr());
+/// ```
+pub trait TryWriteTo<'a, Ctx = ()> {
+    /// Try to write into a sink using a specific context.
+    fn try_write_to<S: Sink<'a>>(self, sink: &mut S, ctx: Ctx) -> Result<()>;
+}
+
+/// Adapts a `TryWrite` value to [`TryWriteTo`](trait.TryWriteTo.html), so existing
+/// types can be written into any sink.
+///
+/// The value is always copied, into the scratch buffer of a `GatherWriter`.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+///
+/// let mut segments = [&[][..]; 2];
+/// let mut scratch = [0u8; 16];
+/// let mut writer = GatherWriter::new(&mut segments, &mut scratch, 64);
+/// Copied(0xdeadbeef_u32).try_write_to(&mut writer, BE).unwrap();
+/// assert_eq!(writer.finish().unwrap(), [&[0xde, 0xad, 0xbe, 0xef][..]]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Copied<T>(pub T);
+
+impl<'a, T, Ctx> TryWriteTo<'a, Ctx> for Copied<T>
+where
+    T: TryWrite<Ctx>,
+{
+    #[inline]
+    fn try_write_to<S: Sink<'a>>(self, sink: &mut S, ctx: Ctx) -> Result<()> {
+        sink.put_with(self.0, ctx)
+    }
+}
+
+/// A sink copying everything into a byte slice.
+#[derive(Debug)]
+pub struct SliceWriter<'b> {
+    bytes: &'b mut [u8],
+    offset: usize,
+}
+
+impl<'b> SliceWriter<'b> {
+    /// Creates a sink writing into the head of the bytes.
+    #[inline]
+    pub fn new(bytes: &'b mut [u8]) -> Self {
+        SliceWriter { bytes, offset: 0 }
+    }
+}
+
+impl<'a> Sink<'a> for SliceWriter<'_> {
+    #[inline]
+    fn put_with<T, Ctx>(&mut self, t: T, ctx: Ctx) -> Result<()>
+    where
+        T: TryWrite<Ctx>,
+    {
+        self.bytes.write_with(&mut self.offset, t, ctx)
+    }
+
+    #[inline]
+    fn put_bytes(&mut self, bytes: &'a [u8]) -> Result<()> {
+        self.bytes.write_with(&mut self.offset, bytes, ())
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.offset
+    }
+}
+
+/// A sink producing a gather list for vectored output.
+///
+/// Byte slices of at least `threshold` bytes become segments of their own, referencing
+/// the input. Everything else is packed into the scratch buffer, whose consecutive
+/// runs become segments in between.
+///
+/// Both the segment list and the scratch buffer are provided by the caller, so no
+/// allocation is needed. The segments can be converted to `IoSlice`s for a vectored
+/// write, or read back with [`Segments`](struct.Segments.html).
+///
+/// See [`TryWriteTo`](trait.TryWriteTo.html) for an example.
+#[derive(Debug)]
+pub struct GatherWriter<'s, 'a> {
+    segments: &'s mut [&'a [u8]],
+    count: usize,
+    scratch: &'a mut [u8],
+    pending: usize,
+    threshold: usize,
+    len: usize,
+}
+
+impl<'s, 'a> GatherWriter<'s, 'a> {
+    /// Creates a sink filling the segment list, packing small values into the scratch
+    /// buffer and referencing byte slices of at least `threshold` bytes.
+    #[inline]
+    pub fn new(segments: &'s mut [&'a [u8]], scratch: &'a mut [u8], threshold: usize) -> Self {
+        GatherWriter {
+            segments,
+            count: 0,
+            scratch,
+            pending: 0,
+            threshold,
+            len: 0,
+        }
+    }
+
+    /// Finishes writing, returning the gather list.
+    #[inline]
+    pub fn finish(mut self) -> Result<&'s [&'a [u8]]> {
+        self.flush()?;
+
+        let GatherWriter {
+            segments, count, ..
+        } = self;
+
+        Ok(&segments[..count])
+    }
+
+    /// Moves the bytes packed into the scratch buffer so far into a segment.
+    fn flush(&mut self) -> Result<()> {
+        if self.pending == 0 {
+            return Ok(());
+        }
+
+        let scratch = mem::take(&mut self.scratch);
+        let (head, tail) = scratch.split_at_mut(self.pending);
+        self.scratch = tail;
+        self.pending = 0;
+
+        self.push(head)
+    }
+
+    #[inline]
+    fn push(&mut self, segment: &'a [u8]) -> Result<()> {
+        let slot = self.segments.get_mut(self.count).ok_or(Error::BadInput {
+            err: "Too many segments",
+        })?;
+        *slot = segment;
+        self.count += 1;
+
+        Ok(())
+    }
+}
+
+impl<'a> Sink<'a> for GatherWriter<'_, 'a> {
+    #[inline]
+    fn put_with<T, Ctx>(&mut self, t: T, ctx: Ctx) -> Result<()>
+    where
+        T: TryWrite<Ctx>,
+    {
+        let offset = &mut self.pending;
+        let start = *offset;
+        self.scratch.write_with(offset, t, ctx)?;
+        self.len += *offset - start;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn put_bytes(&mut self, bytes: &'a [u8]) -> Result<()> {
+        if bytes.is_empty() || bytes.len() < self.threshold {
+            return self.put_with(bytes, ());
+        }
+
+        self.flush()?;
+        self.push(bytes)?;
+        self.len += bytes.len();
+
+        Ok(())
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
@@ -11,6 +11,17 @@
 //!
 //! `Byte` is a `no_std` library; it can be used in any `#![no_std]` situation or crate.
 //!
+//! Enable the `alloc` feature to read into owned types such as `String`, `CString` and
+//! `Vec<T>`, or the `heapless` feature to read into `heapless::Vec<T, N>` and
+//! `heapless::String<N>` without an allocator.
+//!
+//! Enable the `bytes` feature to read from any `bytes::Buf` and write into any
+//! `bytes::BufMut` with [`BufExt`](trait.BufExt.html) and [`BufMutExt`](trait.BufMutExt.html).
+//! This feature requires `std`.
+//!
+//! Enable the `serde` feature to encode types deriving `Serialize` and `Deserialize` with
+//! the compact binary format in the [`format`](format/index.html) module.
+//!
 //! # Overview
 //!
 //! `Byte` is designed for encoding or decoding binary data in a fast and low level way.
@@ -53,9 +64,25 @@
 //! `Byte` supports serializing and deserializing language primitives by default.
 //!
 //! - `&str` (with `Str` context)
+//! - `Utf16Str` (with `Utf16` context)
+//! - `AsciiStr`, `Latin1Str` and `LossyStr` (with `Ascii`, `Latin1` and `Lossy` context)
 //! - `&[u8]` (with `Byte` context)
+//! - `&CStr` (with `Nul` context)
 //! - `u8`, `i8`, `u64`, `f64` ... (with `Endian` context)
+//! - `F16`, `BF16` (with `Endian` context, half-precision floats)
+//! - `Fixed<I, FRAC>` (with `Endian` context, fixed-point numbers such as `Q15`)
 //! - `bool`
+//! - `Option<T>` (with `Optional` context)
+//! - `[T; N]` (with the context of `T`, in place with `read_into()` only)
+//! - `()` (with `Magic` and `Const` context, verifying constant fields)
+//! - `Mixed<T>` (with `Order` context, 32-bit and 64-bit numbers in word-swapped byte orders)
+//! - `Endian` (with `Detect` context, from a byte order mark or magic number)
+//! - `Validated<T>` (with `Validate` context, checking ranges and predicates)
+//! - `Table<T>` (with `Seq` context, random access to fixed-size records)
+//! - `Ptr<T, O>` (with `Follow` context, resolving offsets against a `Base` buffer)
+//! - `Vec<T>`, `String` and `Cow<[u8]>` (with `Seq` context, requires `alloc`)
+//! - `Box<T>` (with the context of `T`, requires `alloc`)
+//! - `heapless::Vec<T, N>` and `heapless::String<N>` (with `Seq` context, requires `heapless`)
 //!
 //! # Define custom serializable/deserializable types
 //!
@@ -129,9 +156,28 @@
 #![no_std]
 #![forbid(unsafe_code)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "bytes")]
+extern crate std;
+
+#[cfg(feature = "bytes")]
+mod buf;
 pub mod ctx;
+#[cfg(feature = "serde")]
+pub mod format;
+mod gather;
+mod macros;
+mod segments;
+mod table;
+#[cfg(feature = "bytes")]
+pub use buf::{BufExt, BufMutExt};
+use core::fmt;
 use core::marker::PhantomData;
 pub use ctx::{BE, LE};
+pub use gather::{Copied, GatherWriter, Sink, SliceWriter, TryWriteTo};
+pub use segments::Segments;
+pub use table::{Chunks, Table, TableIter};
 
 /// A specialized Result type for `Byte`
 pub type Result<T> = core::result::Result<T, Error>;
@@ -143,10 +189,19 @@ pub type Result<T> = core::result::Result<T, Error>;
 /// - `Error::BadInput` and `Error::Incomplete` will be returned when `try_read()` or
 /// `try_write()` finds the bytes are invalid or not long enough to determine their validity.
 ///
+/// - `Error::Invalid` will be returned when a value read or written with the `Validate`
+///   context fails its check.
+///
+/// - `Error::BadConst` and `Error::BadMagic` will be returned when a constant field read
+///   with the `Const` or `Magic` context doesn't have the expected value.
+///
 /// Note that we usually use `bytes.read()` in `try_read()` which may return `Error::BadOffset`,
 /// indicating incomplete data. So the error will automatically be converted into
 /// `Error::Incomplete` if you use `bytes.read()` (the same applies to `write()`).
+///
+/// More variants may be added in the future, so matches need a wildcard arm.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[non_exhaustive]
 pub enum Error {
     /// The requested data is bigger than the available range
     Incomplete,
@@ -154,8 +209,45 @@ pub enum Error {
     BadOffset(usize),
     /// The requested data content is invalid
     BadInput { err: &'static str },
+    /// The value is well-formed but fails validation
+    Invalid { err: &'static str },
+    /// A constant number doesn't have the expected value; both are zero-extended to `u64`
+    BadConst { expected: u64, found: u64 },
+    /// Magic bytes don't match, differing first at `offset` within the magic
+    BadMagic {
+        offset: usize,
+        expected: u8,
+        found: u8,
+    },
 }
 
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Incomplete => f.write_str("incomplete data"),
+            Error::BadOffset(offset) => write!(f, "bad offset {}", offset),
+            Error::BadInput { err } => write!(f, "bad input: {}", err),
+            Error::Invalid { err } => write!(f, "invalid value: {}", err),
+            Error::BadConst { expected, found } => write!(
+                f,
+                "bad constant: expected {:#x}, found {:#x}",
+                expected, found
+            ),
+            Error::BadMagic {
+                offset,
+                expected,
+                found,
+            } => write!(
+                f,
+                "bad magic at byte {}: expected {:#04x}, found {:#04x}",
+                offset, expected, found
+            ),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
 /// A helper function that checks whether the given length exceeded the length
 /// of the slice; returns `Err(Error::Incomplete)` otherwise.
 ///
@@ -211,6 +303,32 @@ where
     fn try_read(bytes: &'a [u8], ctx: Ctx) -> Result<(Self, usize)>;
 }
 
+/// A data structure that can be deserialized in place, reusing an existing value.
+///
+/// This avoids reinitializing large arrays and lets collections such as `Vec` and
+/// `String` reuse their buffers. Types implementing this trait can be `read_into()`
+/// from a byte slice.
+///
+/// If reading fails, the value may have been partially overwritten.
+pub trait TryReadInto<'a, Ctx = ()> {
+    /// Try to read from a byte slice into `self` using a specific context.
+    ///
+    /// If successful, `try_read_into()` should return the number of bytes consumed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use byte::*;
+    ///
+    /// let bytes: &[u8] = &[0x00, 0x01, 0x00, 0x02];
+    ///
+    /// let mut array = [0u16; 2];
+    /// assert_eq!(array.try_read_into(bytes, BE), Ok(4));
+    /// assert_eq!(array, [1, 2]);
+    /// ```
+    fn try_read_into(&mut self, bytes: &'a [u8], ctx: Ctx) -> Result<usize>;
+}
+
 /// A data structure that can be serialized.
 /// Types implement this trait can be `write()` into a byte slice.
 pub trait TryWrite<Ctx = ()> {
@@ -241,6 +359,75 @@ pub trait TryWrite<Ctx = ()> {
     fn try_write(self, bytes: &mut [u8], ctx: Ctx) -> Result<usize>;
 }
 
+/// A data structure that can be serialized by reference.
+///
+/// Unlike `TryWrite`, `encode()` borrows the value, so it can be written repeatedly
+/// or from inside a collection without cloning. Every `&T` where `T: Encode` is
+/// `TryWrite`, so the reference can be passed to `write()`.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// struct Packet {
+///     id: u16,
+///     items: Vec<u16>,
+/// }
+///
+/// impl Encode<Endian> for Packet {
+///     fn encode(&self, bytes: &mut [u8], endian: Endian) -> Result<usize> {
+///         let offset = &mut 0;
+///         let seq = Seq {
+///             len: SeqLen::CountPrefix(Prefix::U8),
+///             max: 16,
+///             ctx: endian,
+///         };
+///
+///         bytes.write_with(offset, self.id, endian)?;
+///         bytes.write_with(offset, &self.items, seq)?;
+///
+///         Ok(*offset)
+///     }
+/// }
+///
+/// let packet = Packet { id: 1, items: vec![2, 3] };
+///
+/// let mut bytes = [0u8; 7];
+/// bytes.write_with(&mut 0, &packet, BE).unwrap();
+/// assert_eq!(bytes, [0, 1, 2, 0, 2, 0, 3]);
+///
+/// // Still owned, so it can be written again
+/// assert_eq!(packet.encode(&mut bytes, LE), Ok(7));
+/// # }
+/// ```
+pub trait Encode<Ctx = ()> {
+    /// Try to write to a byte slice using a specific context, borrowing the value.
+    ///
+    /// If successful `encode()` should return the number of bytes written.
+    fn encode(&self, bytes: &mut [u8], ctx: Ctx) -> Result<usize>;
+}
+
+impl<T, Ctx> TryWrite<Ctx> for &T
+where
+    T: Encode<Ctx> + ?Sized,
+{
+    #[inline]
+    fn try_write(self, bytes: &mut [u8], ctx: Ctx) -> Result<usize> {
+        self.encode(bytes, ctx)
+    }
+}
+
+/// A data structure whose encoded size is always the same.
+///
+/// Records of a `Table` must be `FixedSize`, so that they can be located by index.
+pub trait FixedSize {
+    /// The number of bytes the value is encoded in.
+    const SIZE: usize;
+}
+
 /// Extension methods for byte slices.
 ///
 /// # Offset
@@ -415,6 +602,71 @@ impl<Ctx> BytesExt<Ctx> for [u8] {
     }
 }
 
+/// Extension methods for reading into existing values from byte slices.
+///
+/// The offset works as in [`BytesExt`](trait.BytesExt.html).
+pub trait ReadIntoExt<Ctx> {
+    /// Reads into an existing value using the default context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use byte::*;
+    ///
+    /// let bytes: &[u8] = &[0, 1];
+    ///
+    /// let mut flags = [false; 2];
+    /// bytes.read_into(&mut 0, &mut flags).unwrap();
+    /// assert_eq!(flags, [false, true]);
+    /// ```
+    fn read_into<'a, T>(&'a self, offset: &mut usize, value: &mut T) -> Result<()>
+    where
+        T: TryReadInto<'a, Ctx> + ?Sized,
+        Ctx: Default,
+    {
+        self.read_into_with(offset, value, Default::default())
+    }
+
+    /// Reads into an existing value specifying the context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use byte::*;
+    ///
+    /// let bytes: &[u8] = &[0x00, 0xff, 0xff, 0x00];
+    ///
+    /// let mut nums = [0u16; 2];
+    /// bytes.read_into_with(&mut 0, &mut nums, BE).unwrap();
+    /// assert_eq!(nums, [0x00ff, 0xff00]);
+    /// ```
+    fn read_into_with<'a, T>(&'a self, offset: &mut usize, value: &mut T, ctx: Ctx) -> Result<()>
+    where
+        T: TryReadInto<'a, Ctx> + ?Sized;
+}
+
+impl<Ctx> ReadIntoExt<Ctx> for [u8] {
+    fn read_into_with<'a, T>(&'a self, offset: &mut usize, value: &mut T, ctx: Ctx) -> Result<()>
+    where
+        T: TryReadInto<'a, Ctx> + ?Sized,
+    {
+        let slice = self;
+
+        if *offset > slice.len() {
+            return Err(Error::BadOffset(*offset));
+        };
+
+        match value.try_read_into(&slice[*offset..], ctx) {
+            Ok(size) => {
+                *offset += size;
+                Ok(())
+            }
+            Err(Error::BadOffset(_)) => Err(Error::Incomplete),
+            Err(err) => Err(err),
+        }
+    }
+}
+
 /// An iterator that reads values of the same type from a byte slice.
 ///
 /// # Example
@@ -0,0 +1,163 @@
+/// Assembles a byte array from typed items, computing its length at compile time.
+///
+/// Items are separated by commas:
+///
+/// - `u8 expr` and `i8 expr`
+/// - `<type> BE expr` and `<type> LE expr` for other numbers, e.g. `u16 BE 5`
+/// - `bool expr`, written as `0xff` or `0` like `TryWrite for bool`
+/// - `"literal"`, the UTF-8 bytes of a string literal
+/// - `[byte; n]`, `n` repetitions of a byte
+///
+/// The length only depends on the types, string literals and repetition counts, so
+/// those must be constant. The macro can be used in `const` context if the values
+/// are constant too.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+///
+/// const HEADER: [u8; 8] = bytes!(u16 BE 5, "HELLO", bool false);
+/// assert_eq!(HEADER, [0, 5, b'H', b'E', b'L', b'L', b'O', 0]);
+///
+/// let id = 0xdeadbeef;
+/// let bytes = bytes!(u32 LE id, [0; 2], u8 1);
+/// assert_eq!(bytes, [0xef, 0xbe, 0xad, 0xde, 0, 0, 1]);
+/// ```
+#[macro_export]
+macro_rules! bytes {
+    (@len $acc:expr;) => { $acc };
+    (@len $acc:expr; bool $e:expr $(, $($rest:tt)*)?) => {
+        $crate::bytes!(@len $acc + 1; $($($rest)*)?)
+    };
+    (@len $acc:expr; u8 $e:expr $(, $($rest:tt)*)?) => {
+        $crate::bytes!(@len $acc + 1; $($($rest)*)?)
+    };
+    (@len $acc:expr; i8 $e:expr $(, $($rest:tt)*)?) => {
+        $crate::bytes!(@len $acc + 1; $($($rest)*)?)
+    };
+    (@len $acc:expr; $ty:ident BE $e:expr $(, $($rest:tt)*)?) => {
+        $crate::bytes!(@len $acc + ::core::mem::size_of::<$ty>(); $($($rest)*)?)
+    };
+    (@len $acc:expr; $ty:ident LE $e:expr $(, $($rest:tt)*)?) => {
+        $crate::bytes!(@len $acc + ::core::mem::size_of::<$ty>(); $($($rest)*)?)
+    };
+    (@len $acc:expr; $s:literal $(, $($rest:tt)*)?) => {
+        $crate::bytes!(@len $acc + $s.len(); $($($rest)*)?)
+    };
+    (@len $acc:expr; [$byte:expr; $n:expr] $(, $($rest:tt)*)?) => {
+        $crate::bytes!(@len $acc + $n; $($($rest)*)?)
+    };
+
+    (@write $bytes:ident $i:ident;) => {};
+    (@write $bytes:ident $i:ident; bool $e:expr $(, $($rest:tt)*)?) => {
+        $bytes[$i] = if $e { 0xff } else { 0 };
+        $i += 1;
+        $crate::bytes!(@write $bytes $i; $($($rest)*)?);
+    };
+    (@write $bytes:ident $i:ident; u8 $e:expr $(, $($rest:tt)*)?) => {
+        $bytes[$i] = $e;
+        $i += 1;
+        $crate::bytes!(@write $bytes $i; $($($rest)*)?);
+    };
+    (@write $bytes:ident $i:ident; i8 $e:expr $(, $($rest:tt)*)?) => {
+        $crate::bytes!(@copy $bytes $i; <i8>::to_ne_bytes($e));
+        $crate::bytes!(@write $bytes $i; $($($rest)*)?);
+    };
+    (@write $bytes:ident $i:ident; $ty:ident BE $e:expr $(, $($rest:tt)*)?) => {
+        $crate::bytes!(@copy $bytes $i; <$ty>::to_be_bytes($e));
+        $crate::bytes!(@write $bytes $i; $($($rest)*)?);
+    };
+    (@write $bytes:ident $i:ident; $ty:ident LE $e:expr $(, $($rest:tt)*)?) => {
+        $crate::bytes!(@copy $bytes $i; <$ty>::to_le_bytes($e));
+        $crate::bytes!(@write $bytes $i; $($($rest)*)?);
+    };
+    (@write $bytes:ident $i:ident; $s:literal $(, $($rest:tt)*)?) => {
+        $crate::bytes!(@copy $bytes $i; $s.as_bytes());
+        $crate::bytes!(@write $bytes $i; $($($rest)*)?);
+    };
+    (@write $bytes:ident $i:ident; [$byte:expr; $n:expr] $(, $($rest:tt)*)?) => {
+        let end = $i + $n;
+        while $i < end {
+            $bytes[$i] = $byte;
+            $i += 1;
+        }
+        $crate::bytes!(@write $bytes $i; $($($rest)*)?);
+    };
+
+    // Copies with a `while` loop, as iterators and `copy_from_slice` are not const.
+    (@copy $bytes:ident $i:ident; $src:expr) => {
+        let src = $src;
+        let mut j = 0;
+        while j < src.len() {
+            $bytes[$i] = src[j];
+            $i += 1;
+            j += 1;
+        }
+    };
+
+    ($($items:tt)*) => {{
+        const LEN: usize = $crate::bytes!(@len 0; $($items)*);
+        #[allow(unused_mut)]
+        let mut bytes = [0u8; LEN];
+        #[allow(unused_mut, unused_variables)]
+        let mut i = 0;
+        $crate::bytes!(@write bytes i; $($items)*);
+        bytes
+    }};
+}
+
+/// Reads values in sequence, binding each one to a local variable.
+///
+/// Each item is `name: Type = ctx`, or `name: Type` to use the default context.
+/// Contexts can refer to the values read before them. The values are read with
+/// `BytesExt::read_with()`, and the first error is returned with `?` after rolling
+/// the offset back to where it was before the macro.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// fn parse<'a>(bytes: &'a [u8], offset: &mut usize) -> Result<(&'a str, bool)> {
+///     read!(bytes, offset;
+///         len: u16 = BE,
+///         name: &str = Str::Len(len as usize),
+///         enabled: bool,
+///     );
+///
+///     Ok((name, enabled))
+/// }
+///
+/// let bytes = bytes!(u16 BE 5, "HELLO", bool false);
+///
+/// let offset = &mut 0;
+/// assert_eq!(parse(&bytes, offset), Ok(("HELLO", false)));
+/// assert_eq!(*offset, 8);
+///
+/// let offset = &mut 0;
+/// assert_eq!(parse(&bytes[..7], offset), Err(Error::Incomplete));
+/// assert_eq!(*offset, 0);
+/// ```
+#[macro_export]
+macro_rules! read {
+    (@read $bytes:ident, $offset:ident, $start:ident;) => {
+        $crate::read!(@read $bytes, $offset, $start; ::core::default::Default::default())
+    };
+    (@read $bytes:ident, $offset:ident, $start:ident; $ctx:expr) => {
+        $crate::BytesExt::read_with($bytes, $offset, $ctx).map_err(|err| {
+            *$offset = $start;
+            err
+        })?
+    };
+
+    ($bytes:expr, $offset:expr; $($name:ident : $ty:ty $(= $ctx:expr)?),* $(,)?) => {
+        let bytes: &[u8] = &$bytes[..];
+        let offset: &mut usize = &mut *$offset;
+        let start = *offset;
+        $(
+            let $name: $ty = $crate::read!(@read bytes, offset, start; $($ctx)?);
+        )*
+    };
+}
@@ -0,0 +1,215 @@
+use crate::{BytesExt, Error, Result, TryRead};
+
+/// Size of the stack buffer used for values spanning segments.
+const STACK_LEN: usize = 64;
+
+/// Input split into several byte slices, such as the two halves of a ring buffer
+/// or the buffers of a scatter-gather queue.
+///
+/// Offsets count from the start of the first segment, as if the segments were
+/// concatenated.
+///
+/// - `read_with()` reads owned values. A value lying within one segment is read in
+///   place, and a value spanning segments is read from a copy of up to 64 bytes
+///   on the stack.
+///
+/// - `borrow_with()` reads values borrowing from the input, such as `&str` or `&[u8]`,
+///   and returns an error if the value would span segments or reaches the end of a
+///   segment followed by more bytes.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// // The two halves of a ring buffer
+/// let ring = b"llo!\0\x00\x05he";
+/// let parts = [&ring[5..], &ring[..5]];
+/// let segments = Segments::new(&parts);
+///
+/// let offset = &mut 0;
+/// let len: u16 = segments.read_with(offset, BE).unwrap();
+/// assert_eq!(len, 5);
+/// assert_eq!(
+///     segments.borrow_with::<&str, _>(offset, Str::Len(5)),
+///     Err(Error::BadInput { err: "Value spans segments" })
+/// );
+/// assert_eq!(*offset, 2);
+///
+/// let offset = &mut 4;
+/// let str: &str = segments.borrow_with(offset, Str::Delimiter(NULL)).unwrap();
+/// assert_eq!(str, "llo!");
+/// assert_eq!(*offset, 9);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Segments<'s, 'a> {
+    segments: &'s [&'a [u8]],
+}
+
+impl<'s, 'a> Segments<'s, 'a> {
+    /// Creates the input from its segments in order.
+    #[inline]
+    pub fn new(segments: &'s [&'a [u8]]) -> Self {
+        Segments { segments }
+    }
+
+    /// Returns the total length of the segments.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.segments.iter().map(|segment| segment.len()).sum()
+    }
+
+    /// Returns `true` if the segments contain no bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segments.iter().all(|segment| segment.is_empty())
+    }
+
+    /// Reads an owned value using the default context.
+    #[inline]
+    pub fn read<T, Ctx>(&self, offset: &mut usize) -> Result<T>
+    where
+        T: for<'b> TryRead<'b, Ctx>,
+        Ctx: Default + Clone,
+    {
+        self.read_with(offset, Default::default())
+    }
+
+    /// Reads an owned value specifying the context, copying it to the stack if it
+    /// spans segments.
+    pub fn read_with<T, Ctx>(&self, offset: &mut usize, ctx: Ctx) -> Result<T>
+    where
+        T: for<'b> TryRead<'b, Ctx>,
+        Ctx: Clone,
+    {
+        let (segment, local, spans) = self.locate(*offset)?;
+
+        let segment = &segment[local..];
+        // A value ending before the end of the segment can't depend on the segments
+        // after it, otherwise it has to be read again from a copy.
+        match T::try_read(segment, ctx.clone()) {
+            Ok((value, size)) if size < segment.len() || !spans => {
+                *offset += size;
+                return Ok(value);
+            }
+            Err(err) if !spans => return Err(incomplete(err)),
+            _ => (),
+        }
+
+        let mut stack = [0u8; STACK_LEN];
+        let len = self.copy(*offset, &mut stack);
+        let truncated = self.len() - *offset > len;
+        let (value, size) = match T::try_read(&stack[..len], ctx) {
+            Ok((value, size)) if size < len || !truncated => (value, size),
+            Err(Error::Incomplete) | Err(Error::BadOffset(_)) if !truncated => {
+                return Err(Error::Incomplete)
+            }
+            // The value may continue past the copy.
+            Ok(_) | Err(Error::Incomplete) | Err(Error::BadOffset(_)) => {
+                return Err(Error::BadInput {
+                    err: "Value is too long to copy across segments",
+                })
+            }
+            Err(err) => return Err(err),
+        };
+        *offset += size;
+
+        Ok(value)
+    }
+
+    /// Reads a value borrowing from the input, which must lie within one segment.
+    #[inline]
+    pub fn borrow<T, Ctx>(&self, offset: &mut usize) -> Result<T>
+    where
+        T: TryRead<'a, Ctx>,
+        Ctx: Default,
+    {
+        self.borrow_with(offset, Default::default())
+    }
+
+    /// Reads a value borrowing from the input specifying the context.
+    ///
+    /// Returns an error if the value would span segments. A value ending exactly at
+    /// the end of a segment followed by more bytes is rejected too, as it can't be
+    /// told apart from a value cut short by the segment boundary.
+    pub fn borrow_with<T, Ctx>(&self, offset: &mut usize, ctx: Ctx) -> Result<T>
+    where
+        T: TryRead<'a, Ctx>,
+    {
+        let (segment, local, spans) = self.locate(*offset)?;
+        let segment = &segment[local..];
+
+        // As in `read_with()`, a value reaching the end of the segment may continue
+        // in the next one.
+        let (value, size) = match T::try_read(segment, ctx) {
+            Ok((_, size)) if size == segment.len() && spans => {
+                return Err(Error::BadInput {
+                    err: "Value spans segments",
+                })
+            }
+            Err(Error::Incomplete) | Err(Error::BadOffset(_)) if spans => {
+                return Err(Error::BadInput {
+                    err: "Value spans segments",
+                })
+            }
+            result => result.map_err(incomplete)?,
+        };
+        *offset += size;
+
+        Ok(value)
+    }
+
+    /// Returns the segment containing the byte at the offset, the offset within that
+    /// segment, and whether any bytes follow in later segments.
+    fn locate(&self, offset: usize) -> Result<(&'a [u8], usize, bool)> {
+        let mut local = offset;
+        for (index, segment) in self.segments.iter().enumerate() {
+            if local < segment.len() {
+                let spans = self.segments[index + 1..]
+                    .iter()
+                    .any(|segment| !segment.is_empty());
+                return Ok((segment, local, spans));
+            }
+            local -= segment.len();
+        }
+
+        if local == 0 {
+            Ok((&[], 0, false))
+        } else {
+            Err(Error::BadOffset(offset))
+        }
+    }
+
+    /// Copies the bytes from the offset into `dst`, returning the number of bytes
+    /// copied.
+    fn copy(&self, offset: usize, dst: &mut [u8]) -> usize {
+        let mut skip = offset;
+        let copied = &mut 0;
+        for segment in self.segments {
+            if skip >= segment.len() {
+                skip -= segment.len();
+                continue;
+            }
+            let segment = &segment[skip..];
+            skip = 0;
+            let len = segment.len().min(dst.len() - *copied);
+            // Can't fail as `len` fits the remaining space.
+            let _ = dst.write_with(copied, &segment[..len], ());
+            if *copied == dst.len() {
+                break;
+            }
+        }
+
+        *copied
+    }
+}
+
+/// Reports running out of bytes as `Error::Incomplete`, as `BytesExt` does.
+#[inline]
+fn incomplete(err: Error) -> Error {
+    match err {
+        Error::BadOffset(_) => Error::Incomplete,
+        err => err,
+    }
+}
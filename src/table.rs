@@ -0,0 +1,349 @@
+use crate::ctx::{read_prefix, Seq, SeqLen};
+use crate::{check_len, Error, FixedSize, Result, TryRead};
+use core::cmp::Ordering;
+use core::fmt;
+use core::marker::PhantomData;
+
+/// A zero-copy table of equal-size records, decoded on access.
+///
+/// `Table` is read with a `Seq` context giving the number of records (or their
+/// total byte length) and the context of each record. Records are located by
+/// `T::SIZE`, so any record can be decoded without reading the ones before it.
+///
+/// # Example
+///
+/// ```
+/// use byte::*;
+/// use byte::ctx::*;
+///
+/// // An index of (key, value) records sorted by key
+/// #[derive(Debug, PartialEq)]
+/// struct Entry {
+///     key: u16,
+///     value: u8,
+/// }
+///
+/// impl FixedSize for Entry {
+///     const SIZE: usize = 3;
+/// }
+///
+/// impl<'a> TryRead<'a, Endian> for Entry {
+///     fn try_read(bytes: &'a [u8], endian: Endian) -> Result<(Self, usize)> {
+///         let offset = &mut 0;
+///         let key = bytes.read_with(offset, endian)?;
+///         let value = bytes.read(offset)?;
+///         Ok((Entry { key, value }, *offset))
+///     }
+/// }
+///
+/// let bytes = bytes!(u8 3, u16 BE 2, u8 20, u16 BE 3, u8 30, u16 BE 5, u8 50);
+/// let seq = Seq {
+///     len: SeqLen::CountPrefix(Prefix::U8),
+///     max: 256,
+///     ctx: BE,
+/// };
+///
+/// let offset = &mut 0;
+/// let table: Table<Entry, Endian> = bytes.read_with(offset, seq).unwrap();
+/// assert_eq!(*offset, 10);
+/// assert_eq!(table.len(), 3);
+/// assert_eq!(table.get(1), Ok(Entry { key: 3, value: 30 }));
+/// assert_eq!(table.binary_search_by_key(&5, |entry| entry.key), Ok(Ok(2)));
+/// assert_eq!(table.binary_search_by_key(&4, |entry| entry.key), Ok(Err(2)));
+/// ```
+pub struct Table<'a, T, Ctx = ()> {
+    bytes: &'a [u8],
+    ctx: Ctx,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<'a, T, Ctx> Table<'a, T, Ctx>
+where
+    T: FixedSize + TryRead<'a, Ctx>,
+    Ctx: Clone,
+{
+    /// Creates a table over `bytes`, which must hold a whole number of records.
+    #[inline]
+    pub fn new(bytes: &'a [u8], ctx: Ctx) -> Result<Self> {
+        if T::SIZE == 0 || !bytes.len().is_multiple_of(T::SIZE) {
+            return Err(Error::BadInput {
+                err: "Length is not a multiple of record size",
+            });
+        }
+
+        Ok(Table {
+            bytes,
+            ctx,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Returns the number of records.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len() / T::SIZE
+    }
+
+    /// Returns `true` if the table has no records.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns the encoded records.
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Decodes the record at `index`, returning `Error::BadInput` if it is out of
+    /// bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Result<T> {
+        if index >= self.len() {
+            return Err(Error::BadInput {
+                err: "Index out of bounds",
+            });
+        }
+
+        let start = index * T::SIZE;
+        T::try_read(&self.bytes[start..start + T::SIZE], self.ctx.clone()).map(|(t, _)| t)
+    }
+
+    /// Returns an iterator decoding the records in order.
+    #[inline]
+    pub fn iter(&self) -> TableIter<'a, T, Ctx> {
+        TableIter {
+            table: self.clone(),
+            front: 0,
+            back: self.len(),
+        }
+    }
+
+    /// Binary searches a table sorted by the key extracted with `f`.
+    ///
+    /// The inner result is the same as `slice::binary_search_by_key()`: `Ok` with
+    /// the index of a matching record, or `Err` with the index where one could be
+    /// inserted. The outer result fails if a visited record can't be decoded.
+    pub fn binary_search_by_key<K, F>(
+        &self,
+        key: &K,
+        mut f: F,
+    ) -> Result<core::result::Result<usize, usize>>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        let mut low = 0;
+        let mut high = self.len();
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match f(&self.get(mid)?).cmp(key) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(Ok(mid)),
+            }
+        }
+
+        Ok(Err(low))
+    }
+
+    /// Returns an iterator over sub-tables of `size` records; the last one may be
+    /// shorter.
+    ///
+    /// Returns `Error::BadInput` if `size` is 0.
+    #[inline]
+    pub fn chunks(&self, size: usize) -> Result<Chunks<'a, T, Ctx>> {
+        if size == 0 {
+            return Err(Error::BadInput {
+                err: "Chunk size is zero",
+            });
+        }
+
+        Ok(Chunks {
+            table: self.clone(),
+            size,
+        })
+    }
+}
+
+impl<T, Ctx: Clone> Clone for Table<'_, T, Ctx> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Table {
+            bytes: self.bytes,
+            ctx: self.ctx.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, Ctx: Copy> Copy for Table<'_, T, Ctx> {}
+
+impl<T, Ctx: PartialEq> PartialEq for Table<'_, T, Ctx> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes && self.ctx == other.ctx
+    }
+}
+
+impl<T, Ctx: Eq> Eq for Table<'_, T, Ctx> {}
+
+impl<T, Ctx: fmt::Debug> fmt::Debug for Table<'_, T, Ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Table")
+            .field("bytes", &self.bytes)
+            .field("ctx", &self.ctx)
+            .finish()
+    }
+}
+
+impl<'a, T, Ctx> TryRead<'a, Seq<Ctx>> for Table<'a, T, Ctx>
+where
+    T: FixedSize + TryRead<'a, Ctx>,
+    Ctx: Clone,
+{
+    #[inline]
+    fn try_read(bytes: &'a [u8], seq: Seq<Ctx>) -> Result<(Self, usize)> {
+        let offset = &mut 0;
+
+        let len = match seq.len {
+            SeqLen::Count(count) => count_len::<T>(count, seq.max)?,
+            SeqLen::CountPrefix(prefix) => {
+                count_len::<T>(read_prefix(bytes, offset, prefix)?, seq.max)?
+            }
+            SeqLen::Bytes(len) => len,
+            SeqLen::BytesPrefix(prefix) => read_prefix(bytes, offset, prefix)?,
+            SeqLen::ToEnd => bytes.len(),
+        };
+
+        let len = check_len(&bytes[*offset..], len)?;
+        let table = Table::new(&bytes[*offset..*offset + len], seq.ctx)?;
+        if table.len() > seq.max {
+            return Err(Error::BadInput {
+                err: "Too many elements",
+            });
+        }
+
+        Ok((table, *offset + len))
+    }
+}
+
+/// Returns the byte length of `count` records, checking it against `max`.
+#[inline]
+fn count_len<T: FixedSize>(count: usize, max: usize) -> Result<usize> {
+    if count > max {
+        return Err(Error::BadInput {
+            err: "Too many elements",
+        });
+    }
+
+    count.checked_mul(T::SIZE).ok_or(Error::BadInput {
+        err: "Too many elements",
+    })
+}
+
+/// An iterator decoding the records of a `Table`.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct TableIter<'a, T, Ctx> {
+    table: Table<'a, T, Ctx>,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T, Ctx> Iterator for TableIter<'a, T, Ctx>
+where
+    T: FixedSize + TryRead<'a, Ctx>,
+    Ctx: Clone,
+{
+    type Item = Result<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Result<T>> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.front += 1;
+        Some(self.table.get(self.front - 1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, Ctx> DoubleEndedIterator for TableIter<'a, T, Ctx>
+where
+    T: FixedSize + TryRead<'a, Ctx>,
+    Ctx: Clone,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Result<T>> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(self.table.get(self.back))
+    }
+}
+
+impl<'a, T, Ctx> ExactSizeIterator for TableIter<'a, T, Ctx>
+where
+    T: FixedSize + TryRead<'a, Ctx>,
+    Ctx: Clone,
+{
+}
+
+/// An iterator over sub-tables of a `Table`.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct Chunks<'a, T, Ctx> {
+    table: Table<'a, T, Ctx>,
+    size: usize,
+}
+
+impl<'a, T, Ctx> Iterator for Chunks<'a, T, Ctx>
+where
+    T: FixedSize + TryRead<'a, Ctx>,
+    Ctx: Clone,
+{
+    type Item = Table<'a, T, Ctx>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Table<'a, T, Ctx>> {
+        if self.table.is_empty() {
+            return None;
+        }
+
+        let len = self
+            .table
+            .bytes
+            .len()
+            .min(self.size.saturating_mul(T::SIZE));
+        let (head, tail) = self.table.bytes.split_at(len);
+        self.table.bytes = tail;
+
+        Some(Table {
+            bytes: head,
+            ctx: self.table.ctx.clone(),
+            phantom: PhantomData,
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.table.len().div_ceil(self.size);
+        (len, Some(len))
+    }
+}
+
+impl<'a, T, Ctx> ExactSizeIterator for Chunks<'a, T, Ctx>
+where
+    T: FixedSize + TryRead<'a, Ctx>,
+    Ctx: Clone,
+{
+}
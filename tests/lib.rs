@@ -116,6 +116,73 @@ fn test_str_delimitor() {
         .is_err());
 }
 
+#[test]
+fn test_str_pattern() {
+    let bytes: &[u8] = b"abc\r\ndef\r\n\r\ngh";
+
+    let mut offset = 0;
+    let str: &str = bytes.read_with(&mut offset, Str::Pattern(CRLF)).unwrap();
+    assert_eq!(str, "abc");
+    assert_eq!(offset, 5);
+    let str: &str = bytes.read_with(&mut offset, Str::Pattern(CRLF)).unwrap();
+    assert_eq!(str, "def");
+    let str: &str = bytes.read_with(&mut offset, Str::Pattern(CRLF)).unwrap();
+    assert_eq!(str, "");
+    assert_eq!(offset, 12);
+    assert!(bytes
+        .read_with::<&str>(&mut offset, Str::Pattern(CRLF))
+        .is_err());
+
+    assert_eq!(
+        TryRead::try_read(bytes, Str::PatternUntil(CRLF, 5)).unwrap(),
+        ("abc", 5)
+    );
+    assert_eq!(
+        TryRead::try_read(bytes, Str::PatternUntil(CRLF, 4)).unwrap(),
+        ("abc\r", 4)
+    );
+    assert_eq!(
+        TryRead::try_read(bytes, Str::PatternUntil(CRLF, 2)).unwrap(),
+        ("ab", 2)
+    );
+    assert_eq!(
+        bytes.read_with::<&str>(&mut 0, Str::PatternUntil(b"\r\n\r\n", 3)),
+        Err(Error::BadInput {
+            err: "Pattern is longer than restricted length"
+        })
+    );
+    assert!(bytes.read_with::<&str>(&mut 0, Str::Pattern(b"")).is_err());
+    assert!(bytes
+        .read_with::<&str>(&mut 12, Str::PatternUntil(CRLF, 3))
+        .is_err());
+
+    let mut write = [0xff; 8];
+    assert_eq!(
+        TryWrite::try_write(Encoded("abc"), &mut write, Str::Pattern(CRLF)).unwrap(),
+        5
+    );
+    assert_eq!(&write[..5], b"abc\r\n");
+    assert_eq!(
+        TryWrite::try_write(Encoded("abc\r"), &mut write, Str::Pattern(CRLF)).unwrap(),
+        6
+    );
+    assert_eq!(
+        TryWrite::try_write(Encoded("abcd"), &mut write, Str::PatternUntil(CRLF, 4)).unwrap(),
+        4
+    );
+    assert_eq!(
+        TryWrite::try_write(Encoded("ab"), &mut write, Str::PatternUntil(CRLF, 4)).unwrap(),
+        4
+    );
+    assert!(TryWrite::try_write(Encoded("a\r\nb"), &mut write, Str::Pattern(CRLF)).is_err());
+    assert!(TryWrite::try_write(Encoded("abc"), &mut write, Str::PatternUntil(CRLF, 4)).is_err());
+    assert!(
+        TryWrite::try_write(Encoded("a\r\nb"), &mut write, Str::PatternUntil(CRLF, 4)).is_err()
+    );
+    // "ab" followed by "abab" would be read back as ""
+    assert!(TryWrite::try_write(Encoded("ab"), &mut write, Str::Pattern(b"abab")).is_err());
+}
+
 #[test]
 fn test_str_write() {
     let mut bytes = [0; 20];
@@ -124,8 +191,1764 @@ fn test_str_write() {
     assert_eq!(offset, 12);
     assert_eq!(&bytes[..offset], b"hello world!" as &[u8]);
 
-    let bytes = &mut [0; 10];
-    assert!(bytes.write(&mut 0, "hello world!").is_err());
+    let bytes = &mut [0; 10];
+    assert!(bytes.write(&mut 0, "hello world!").is_err());
+}
+
+#[test]
+fn test_str_write_with() {
+    let mut bytes = [0xff; 8];
+    let mut offset = 0;
+    bytes
+        .write_with(&mut offset, Encoded("abcd"), Str::Delimiter(NULL))
+        .unwrap();
+    assert_eq!(offset, 5);
+    assert_eq!(&bytes[..offset], b"abcd\0");
+    assert_eq!(
+        bytes
+            .read_with::<&str>(&mut 0, Str::Delimiter(NULL))
+            .unwrap(),
+        "abcd"
+    );
+
+    let mut bytes = [0u8; 8];
+    assert_eq!(
+        TryWrite::try_write(Encoded("abcd"), &mut bytes, Str::Len(4)).unwrap(),
+        4
+    );
+    assert_eq!(&bytes[..4], b"abcd");
+    assert!(TryWrite::try_write(Encoded("abcd"), &mut bytes, Str::Len(3)).is_err());
+    assert!(TryWrite::try_write(Encoded("abcd"), &mut bytes, Str::Len(5)).is_err());
+
+    let mut bytes = [0xff; 8];
+    assert_eq!(
+        TryWrite::try_write(Encoded("abcd"), &mut bytes, Str::DelimiterUntil(NULL, 5)).unwrap(),
+        5
+    );
+    assert_eq!(&bytes[..5], b"abcd\0");
+    assert_eq!(
+        TryWrite::try_write(Encoded("abcde"), &mut bytes, Str::DelimiterUntil(NULL, 5)).unwrap(),
+        5
+    );
+    assert_eq!(&bytes[..5], b"abcde");
+    assert!(
+        TryWrite::try_write(Encoded("abcdef"), &mut bytes, Str::DelimiterUntil(NULL, 5)).is_err()
+    );
+
+    assert!([0u8; 8]
+        .write_with(&mut 0, Encoded("ab\0cd"), Str::Delimiter(NULL))
+        .is_err());
+    assert!([0u8; 8]
+        .write_with(&mut 0, Encoded("ab\0cd"), Str::DelimiterUntil(NULL, 6))
+        .is_err());
+    assert!([0u8; 4]
+        .write_with(&mut 0, Encoded("abcd"), Str::Delimiter(NULL))
+        .is_err());
+
+    // Nothing is written on failure
+    let mut bytes = [0xff; 4];
+    assert_eq!(
+        bytes.write_with(&mut 0, Encoded("abcd"), Str::Delimiter(NULL)),
+        Err(Error::Incomplete)
+    );
+    assert!(bytes
+        .write_with(&mut 0, Encoded("a\0b"), Str::Delimiter(NULL))
+        .is_err());
+    assert_eq!(bytes, [0xff; 4]);
+}
+
+#[test]
+fn test_utf16() {
+    let bytes: &[u8] = &[0x00, 0x61, 0xd8, 0x3d, 0xde, 0x00, 0x00, 0x00, 0x00, 0x62];
+
+    let mut offset = 0;
+    let str: Utf16Str = bytes.read_with(&mut offset, Utf16::Nul(BE)).unwrap();
+    assert_eq!(offset, 8);
+    assert_eq!(str.len(), 3);
+    assert_eq!(str, "a\u{1f600}");
+
+    let str: Utf16Str = bytes.read_with(&mut 0, Utf16::Len(1, BE)).unwrap();
+    assert_eq!(str, "a");
+    let str: Utf16Str = bytes.read_with(&mut 0, Utf16::NulUntil(3, BE)).unwrap();
+    assert_eq!(str, "a\u{1f600}");
+    let str: Utf16Str = bytes.read_with(&mut 0, Utf16::NulUntil(5, BE)).unwrap();
+    assert_eq!(str, "a\u{1f600}");
+    let str: Utf16Str = bytes.read_with(&mut 8, Utf16::NulUntil(1, BE)).unwrap();
+    assert_eq!(str, "b");
+    assert!(bytes
+        .read_with::<Utf16Str>(&mut 8, Utf16::NulUntil(5, BE))
+        .is_err());
+    let str: Utf16Str = bytes.read_with(&mut 0, Utf16::Len(1, LE)).unwrap();
+    assert_eq!(str, "\u{6100}");
+
+    // Split surrogate pair
+    assert!(bytes
+        .read_with::<Utf16Str>(&mut 0, Utf16::Len(2, BE))
+        .is_err());
+    // Swapped surrogate pair
+    assert!([0x00, 0xde, 0x3d, 0xd8]
+        .read_with::<Utf16Str>(&mut 0, Utf16::Len(2, LE))
+        .is_err());
+    assert!(bytes.read_with::<Utf16Str>(&mut 8, Utf16::Nul(BE)).is_err());
+    assert!(bytes
+        .read_with::<Utf16Str>(&mut 0, Utf16::Len(6, BE))
+        .is_err());
+}
+
+#[test]
+fn test_utf16_write() {
+    let mut bytes = [0xff; 10];
+    let mut offset = 0;
+    bytes
+        .write_with(&mut offset, Encoded("a\u{1f600}"), Utf16::Nul(LE))
+        .unwrap();
+    assert_eq!(offset, 8);
+    assert_eq!(
+        &bytes[..offset],
+        &[0x61, 0x00, 0x3d, 0xd8, 0x00, 0xde, 0x00, 0x00]
+    );
+    let str: Utf16Str = bytes.read_with(&mut 0, Utf16::Nul(LE)).unwrap();
+    assert_eq!(str, "a\u{1f600}");
+
+    assert_eq!(
+        TryWrite::try_write(Encoded("ab"), &mut bytes, Utf16::Len(2, BE)).unwrap(),
+        4
+    );
+    assert_eq!(&bytes[..4], &[0x00, 0x61, 0x00, 0x62]);
+    assert_eq!(
+        TryWrite::try_write(Encoded("ab"), &mut bytes, Utf16::NulUntil(2, BE)).unwrap(),
+        4
+    );
+    assert_eq!(
+        TryWrite::try_write(Encoded("ab"), &mut bytes, Utf16::NulUntil(3, BE)).unwrap(),
+        6
+    );
+
+    assert!(TryWrite::try_write(Encoded("ab"), &mut bytes, Utf16::Len(3, BE)).is_err());
+    assert!(TryWrite::try_write(Encoded("abc"), &mut bytes, Utf16::NulUntil(2, BE)).is_err());
+    assert!(TryWrite::try_write(Encoded("a\0b"), &mut bytes, Utf16::Nul(BE)).is_err());
+    assert!(TryWrite::try_write(Encoded("abcde"), &mut bytes, Utf16::Nul(BE)).is_err());
+
+    // Nothing is written on failure
+    let mut bytes = [0xff; 4];
+    assert_eq!(
+        bytes.write_with(&mut 0, Encoded("ab"), Utf16::Nul(BE)),
+        Err(Error::Incomplete)
+    );
+    assert!(bytes
+        .write_with(&mut 0, Encoded("a\0"), Utf16::Nul(BE))
+        .is_err());
+    assert_eq!(bytes, [0xff; 4]);
+}
+
+#[test]
+fn test_text() {
+    let bytes: &[u8] = b"abc\0d\xe9f\0g\xffh\0";
+
+    let mut offset = 0;
+    let str: AsciiStr = bytes
+        .read_with(&mut offset, Ascii(Str::Delimiter(NULL)))
+        .unwrap();
+    assert_eq!(str, "abc");
+    assert!(bytes
+        .read_with::<AsciiStr>(&mut offset, Ascii(Str::Delimiter(NULL)))
+        .is_err());
+    assert!(bytes
+        .read_with::<&str>(&mut offset, Str::Delimiter(NULL))
+        .is_err());
+
+    let str: Latin1Str = bytes
+        .read_with(&mut offset, Latin1(Str::Delimiter(NULL)))
+        .unwrap();
+    assert_eq!(offset, 8);
+    assert_eq!(str.len(), 3);
+    assert_eq!(str, "d\u{e9}f");
+
+    let str: LossyStr = bytes
+        .read_with(&mut offset, Lossy(Str::Delimiter(NULL)))
+        .unwrap();
+    assert_eq!(offset, 12);
+    assert_eq!(str.as_str(), None);
+    assert_eq!(str, "g\u{fffd}h");
+
+    let str: LossyStr = bytes.read_with(&mut 0, Lossy(Str::Len(3))).unwrap();
+    assert_eq!(str.as_str(), Some("abc"));
+}
+
+#[test]
+fn test_text_write() {
+    let mut bytes = [0xff; 8];
+    assert_eq!(
+        TryWrite::try_write(
+            Encoded("d\u{e9}f"),
+            &mut bytes,
+            Latin1(Str::Delimiter(NULL))
+        )
+        .unwrap(),
+        4
+    );
+    assert_eq!(&bytes[..4], b"d\xe9f\0");
+    assert!(TryWrite::try_write(Encoded("\u{100}"), &mut bytes, Latin1(Str::Len(1))).is_err());
+    assert!(TryWrite::try_write(Encoded("abcdefghi"), &mut bytes, Latin1(Str::Len(9))).is_err());
+
+    assert_eq!(
+        TryWrite::try_write(Encoded("abc"), &mut bytes, Ascii(Str::Len(3))).unwrap(),
+        3
+    );
+    assert!(TryWrite::try_write(Encoded("d\u{e9}f"), &mut bytes, Ascii(Str::Len(4))).is_err());
+
+    assert_eq!(
+        TryWrite::try_write(Encoded("d\u{e9}f"), &mut bytes, Lossy(Str::Len(4))).unwrap(),
+        4
+    );
+    assert_eq!(&bytes[..4], "d\u{e9}f".as_bytes());
+
+    // Nothing is written on failure
+    let mut bytes = [0xff; 4];
+    assert!(bytes
+        .write_with(&mut 0, Encoded("d\u{e9}f\0"), Latin1(Str::Delimiter(NULL)))
+        .is_err());
+    assert_eq!(bytes, [0xff; 4]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_text_alloc() {
+    use std::borrow::Cow;
+
+    let bytes: &[u8] = b"d\xe9f\0";
+
+    let str: String = bytes
+        .read_with(&mut 0, Latin1(Str::Delimiter(NULL)))
+        .unwrap();
+    assert_eq!(str, "d\u{e9}f");
+
+    let str: Cow<str> = bytes
+        .read_with(&mut 0, Lossy(Str::Delimiter(NULL)))
+        .unwrap();
+    assert_eq!(str, "d\u{fffd}f");
+
+    let str: Cow<str> = bytes.read_with(&mut 0, Lossy(Str::Len(1))).unwrap();
+    assert!(matches!(str, Cow::Borrowed("d")));
+}
+
+#[test]
+fn test_cstr() {
+    use std::ffi::CStr;
+
+    let bytes: &[u8] = b"abc\0\xff\0efg";
+
+    let mut offset = 0;
+    let str: &CStr = bytes.read(&mut offset).unwrap();
+    assert_eq!(str.to_bytes(), b"abc");
+    assert_eq!(offset, 4);
+    let str: &CStr = bytes.read(&mut offset).unwrap();
+    assert_eq!(str.to_bytes(), b"\xff");
+    assert_eq!(offset, 6);
+    assert!(bytes.read::<&CStr>(&mut offset).is_err());
+
+    assert_eq!(
+        TryRead::try_read(bytes, Nul::Bounded(4)).unwrap(),
+        (c"abc", 4)
+    );
+    assert_eq!(
+        TryRead::try_read(bytes, Nul::Bounded(10)).unwrap(),
+        (c"abc", 4)
+    );
+    assert_eq!(
+        bytes.read_with::<&CStr>(&mut 0, Nul::Bounded(3)),
+        Err(Error::BadInput {
+            err: "CStr is longer than restricted length"
+        })
+    );
+    assert_eq!(
+        bytes.read_with::<&CStr>(&mut 6, Nul::Bounded(4)),
+        Err(Error::Incomplete)
+    );
+
+    let mut bytes = [0xff; 8];
+    assert_eq!(
+        TryWrite::try_write(c"abc", &mut bytes, Nul::Unbounded).unwrap(),
+        4
+    );
+    assert_eq!(&bytes[..4], b"abc\0");
+    assert_eq!(
+        TryWrite::try_write(c"abc", &mut bytes, Nul::Bounded(4)).unwrap(),
+        4
+    );
+    assert!(TryWrite::try_write(c"abc", &mut bytes, Nul::Bounded(3)).is_err());
+    assert!(TryWrite::try_write(c"abcdefgh", &mut bytes, Nul::Unbounded).is_err());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_cstring() {
+    use std::ffi::CString;
+
+    let bytes: &[u8] = b"abc\0efg";
+
+    let str: CString = bytes.read(&mut 0).unwrap();
+    assert_eq!(str.as_bytes(), b"abc");
+
+    let mut write = [0u8; 4];
+    write.write(&mut 0, str).unwrap();
+    assert_eq!(&write, b"abc\0");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_seq() {
+    let bytes: &[u8] = &[0x00, 0x02, 0x00, 0x01, 0x00, 0x02, 0xff];
+
+    let ctx = Seq {
+        len: SeqLen::CountPrefix(Prefix::U16(BE)),
+        max: 2,
+        ctx: BE,
+    };
+    let offset = &mut 0;
+    let list: Vec<u16> = bytes.read_with(offset, ctx).unwrap();
+    assert_eq!(list, [1, 2]);
+    assert_eq!(*offset, 6);
+
+    let mut write = [0u8; 6];
+    assert_eq!(
+        TryWrite::try_write(list.clone(), &mut write, ctx).unwrap(),
+        6
+    );
+    assert_eq!(write, bytes[..6]);
+
+    let ctx = Seq {
+        len: SeqLen::BytesPrefix(Prefix::U8),
+        max: 8,
+        ctx: LE,
+    };
+    let list: Vec<u16> = [0x04, 0x01, 0x00, 0x02, 0x00]
+        .read_with(&mut 0, ctx)
+        .unwrap();
+    assert_eq!(list, [1, 2]);
+    assert_eq!(TryWrite::try_write(list, &mut write, ctx).unwrap(), 5);
+    assert_eq!(write[..5], [0x04, 0x01, 0x00, 0x02, 0x00]);
+
+    let ctx = Seq {
+        len: SeqLen::ToEnd,
+        max: 8,
+        ctx: BE,
+    };
+    assert_eq!(
+        bytes[..6].read_with::<Vec<u16>>(&mut 2, ctx).unwrap(),
+        [1, 2]
+    );
+    assert_eq!(
+        bytes.read_with::<Vec<u16>>(&mut 2, ctx),
+        Err(Error::Incomplete)
+    );
+
+    let ctx = Seq {
+        len: SeqLen::Count(3),
+        max: 2,
+        ctx: BE,
+    };
+    assert_eq!(
+        bytes.read_with::<Vec<u16>>(&mut 0, ctx),
+        Err(Error::BadInput {
+            err: "Too many elements"
+        })
+    );
+    assert!(TryWrite::try_write(vec![1u16, 2], &mut write, ctx).is_err());
+
+    let ctx = Seq {
+        len: SeqLen::CountPrefix(Prefix::U8),
+        max: 300,
+        ctx: BE,
+    };
+    assert_eq!(
+        TryWrite::try_write(vec![0u8; 256], &mut [0u8; 257], ctx),
+        Err(Error::BadInput {
+            err: "Length doesn't fit prefix"
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_seq_str() {
+    use std::borrow::Cow;
+
+    let bytes: &[u8] = b"\x05hello\x02\xff\xfe";
+    let ctx = Seq {
+        len: SeqLen::BytesPrefix(Prefix::U8),
+        max: 16,
+        ctx: (),
+    };
+
+    let offset = &mut 0;
+    let str: String = bytes.read_with(offset, ctx).unwrap();
+    assert_eq!(str, "hello");
+    assert_eq!(*offset, 6);
+    assert_eq!(
+        bytes.read_with::<String>(offset, ctx),
+        Err(Error::BadInput { err: "UTF8 Error" })
+    );
+    let raw: Cow<[u8]> = bytes.read_with(offset, ctx).unwrap();
+    assert!(matches!(raw, Cow::Borrowed(&[0xff, 0xfe])));
+
+    let str: Cow<str> = bytes.read_with(&mut 0, ctx).unwrap();
+    assert!(matches!(str, Cow::Borrowed("hello")));
+
+    let str: String = bytes.read_with(&mut 1, Str::Len(5)).unwrap();
+    assert_eq!(str, "hello");
+
+    let mut write = [0u8; 6];
+    assert_eq!(
+        TryWrite::try_write(String::from("hello"), &mut write, ctx).unwrap(),
+        6
+    );
+    assert_eq!(write, bytes[..6]);
+
+    let boxed: Box<u16> = bytes.read_with(&mut 0, BE).unwrap();
+    assert_eq!(*boxed, 0x0568);
+    assert_eq!(TryWrite::try_write(boxed, &mut write, LE).unwrap(), 2);
+    assert_eq!(write[..2], [0x68, 0x05]);
+}
+
+#[test]
+#[cfg(feature = "heapless")]
+fn test_heapless() {
+    let bytes: &[u8] = &[0x03, 0x01, 0x02, 0x03];
+    let ctx = Seq {
+        len: SeqLen::CountPrefix(Prefix::U8),
+        max: 8,
+        ctx: BE,
+    };
+
+    let list: heapless::Vec<u8, 3> = bytes.read_with(&mut 0, ctx).unwrap();
+    assert_eq!(list, [1, 2, 3]);
+    assert_eq!(
+        bytes.read_with::<heapless::Vec<u8, 2>>(&mut 0, ctx),
+        Err(Error::BadInput {
+            err: "Capacity exceeded"
+        })
+    );
+
+    let mut write = [0u8; 4];
+    assert_eq!(TryWrite::try_write(list, &mut write, ctx).unwrap(), 4);
+    assert_eq!(write, bytes);
+
+    let bytes: &[u8] = b"\x05hello";
+    let ctx = Seq {
+        len: SeqLen::BytesPrefix(Prefix::U8),
+        max: 8,
+        ctx: (),
+    };
+    let str: heapless::String<5> = bytes.read_with(&mut 0, ctx).unwrap();
+    assert_eq!(str, "hello");
+    assert!(bytes.read_with::<heapless::String<4>>(&mut 0, ctx).is_err());
+    let str: heapless::String<3> = bytes.read_with(&mut 1, Str::Len(3)).unwrap();
+    assert_eq!(str, "hel");
+
+    let mut write = [0u8; 4];
+    assert_eq!(TryWrite::try_write(str, &mut write, ctx).unwrap(), 4);
+    assert_eq!(&write, b"\x03hel");
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_serde() {
+    use byte::format::{from_bytes, to_bytes, Config, Length};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Kind {
+        Empty,
+        Id(u16),
+        Pair(u8, char),
+        Named { flag: bool },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Packet<'a> {
+        name: &'a str,
+        #[serde(with = "serde_bytes_borrowed")]
+        payload: &'a [u8],
+        kinds: Vec<Kind>,
+        checksum: Option<u32>,
+        ratio: f32,
+    }
+
+    mod serde_bytes_borrowed {
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_bytes(bytes)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<&'de [u8], D::Error> {
+            <&[u8]>::deserialize(deserializer)
+        }
+    }
+
+    let packet = Packet {
+        name: "abc",
+        payload: &[0xde, 0xad],
+        kinds: vec![
+            Kind::Empty,
+            Kind::Id(0x0102),
+            Kind::Pair(3, 'x'),
+            Kind::Named { flag: true },
+        ],
+        checksum: None,
+        ratio: 1.5,
+    };
+
+    let config = Config {
+        endian: BE,
+        len: Length::Varint,
+    };
+    let mut bytes = [0u8; 64];
+    let len = to_bytes(&packet, &mut bytes, config).unwrap();
+    assert_eq!(
+        &bytes[..len],
+        b"\x03abc\x02\xde\xad\x04\x00\x01\x01\x02\x02\x03\x00\x00\x00x\x03\xff\x00\x3f\xc0\x00\x00"
+    );
+
+    let (read, size): (Packet, usize) = from_bytes(&bytes, config).unwrap();
+    assert_eq!(read, packet);
+    assert_eq!(size, len);
+    assert_eq!(read.name.as_ptr(), bytes[1..].as_ptr());
+
+    assert_eq!(
+        to_bytes(&packet, &mut bytes[..len - 1], config),
+        Err(Error::Incomplete)
+    );
+    assert_eq!(
+        from_bytes::<Packet>(&bytes[..len - 1], config),
+        Err(Error::Incomplete)
+    );
+
+    let config = Config::default();
+    let len = to_bytes(&(300u16, "hi"), &mut bytes, config).unwrap();
+    assert_eq!(&bytes[..len], b"\x2c\x01\x02\x00\x00\x00hi");
+    assert_eq!(from_bytes(&bytes, config), Ok(((300u16, "hi"), len)));
+
+    let config = Config {
+        endian: LE,
+        len: Length::Varint,
+    };
+    let len = to_bytes(&vec![0u8; 300], &mut bytes[..], config);
+    assert_eq!(len, Err(Error::Incomplete));
+    let mut large = [0u8; 302];
+    assert_eq!(to_bytes(&vec![0u8; 300], &mut large, config), Ok(302));
+    assert_eq!(&large[..2], b"\xac\x02");
+    assert_eq!(from_bytes::<u64>(&[0xff; 11], config), Ok((u64::MAX, 8)));
+    assert_eq!(
+        from_bytes::<Vec<u8>>(&[0xff; 11], config),
+        Err(Error::BadInput {
+            err: "Varint overflows u64"
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "bytes")]
+fn test_buf() {
+    use bytes::{Buf, BytesMut};
+
+    let mut buf = (&[0x00, 0x01][..])
+        .chain(&[0x02][..])
+        .chain(&b"abc\0def"[..]);
+
+    assert_eq!(buf.get_value_with::<u16>(BE).unwrap(), 0x0001);
+    assert_eq!(buf.get_value_with::<u16>(BE).unwrap(), 0x0261);
+    let str: String = buf.get_value_with(Str::Delimiter(NULL)).unwrap();
+    assert_eq!(str, "bc");
+    assert_eq!(buf.get_value_with::<u32>(BE), Err(Error::Incomplete));
+    assert_eq!(buf.remaining(), 3);
+    assert_eq!(
+        buf.get_value_with::<String>(Str::Delimiter(NULL)),
+        Err(Error::Incomplete)
+    );
+    assert_eq!(buf.remaining(), 3);
+
+    let ctx = Seq {
+        len: SeqLen::ToEnd,
+        max: 100,
+        ctx: BE,
+    };
+    let mut buf = (&[1u8; 4][..]).chain(&[2u8; 4][..]);
+    let list: Vec<u8> = buf.get_value_with(ctx).unwrap();
+    assert_eq!(list, [1, 1, 1, 1, 2, 2, 2, 2]);
+    assert!(!buf.has_remaining());
+
+    let ctx = Seq {
+        len: SeqLen::Count(70),
+        max: 100,
+        ctx: BE,
+    };
+    let mut buf = (&[1u8; 40][..]).chain(&[2u8; 40][..]);
+    assert_eq!(
+        buf.get_value_with::<Vec<u8>>(ctx),
+        Err(Error::BadInput {
+            err: "Value is too long to copy across chunks"
+        })
+    );
+    assert_eq!(buf.remaining(), 80);
+
+    let mut buf = BytesMut::new();
+    buf.put_value_with(0x0102_u16, LE).unwrap();
+    let long = [0xaa; 100];
+    buf.put_value(&long[..]).unwrap();
+    buf.put_value(true).unwrap();
+    assert_eq!(buf.len(), 103);
+    assert_eq!(&buf[..2], &[0x02, 0x01]);
+    assert_eq!(&buf[2..102], &long[..]);
+    assert_eq!(buf[102], 0xff);
+
+    let mut bytes = [0u8; 3];
+    let mut buf = &mut bytes[..];
+    buf.put_value_with(0x0102_u16, BE).unwrap();
+    assert_eq!(buf.put_value_with(0x0102_u16, BE), Err(Error::Incomplete));
+    assert_eq!(buf.put_value(&long[..]), Err(Error::Incomplete));
+    assert_eq!(bytes, [0x01, 0x02, 0x00]);
+}
+
+#[test]
+fn test_segments() {
+    let head: &[u8] = &[0x01, 0x02, 0x03];
+    let tail: &[u8] = b"\x04abc\0";
+    let parts = [head, &[], tail];
+    let segments = Segments::new(&parts);
+    assert_eq!(segments.len(), 8);
+    assert!(!segments.is_empty());
+
+    let offset = &mut 1;
+    assert_eq!(
+        segments.read_with::<u32, _>(offset, BE).unwrap(),
+        0x02030461
+    );
+    assert_eq!(*offset, 5);
+    assert_eq!(
+        segments
+            .borrow_with::<&str, _>(offset, Str::Delimiter(NULL))
+            .unwrap(),
+        "bc"
+    );
+    assert_eq!(*offset, 8);
+    assert_eq!(
+        segments.read_with::<u8, _>(offset, BE),
+        Err(Error::Incomplete)
+    );
+    assert_eq!(
+        segments.read_with::<u8, _>(&mut 9, BE),
+        Err(Error::BadOffset(9))
+    );
+
+    let offset = &mut 3;
+    assert_eq!(segments.borrow::<&str, _>(offset), Ok("\u{4}abc"));
+    assert_eq!(
+        segments.borrow::<&str, _>(&mut 0),
+        Err(Error::BadInput {
+            err: "Value spans segments"
+        })
+    );
+    assert_eq!(
+        segments
+            .borrow_with::<&[u8], _>(&mut 0, Bytes::Len(2))
+            .unwrap(),
+        &head[..2]
+    );
+    assert_eq!(
+        segments.borrow_with::<&[u8], _>(&mut 0, Bytes::Len(3)),
+        Err(Error::BadInput {
+            err: "Value spans segments"
+        })
+    );
+    assert_eq!(
+        segments.borrow_with::<&[u8], _>(&mut 2, Bytes::Len(2)),
+        Err(Error::BadInput {
+            err: "Value spans segments"
+        })
+    );
+    assert_eq!(
+        segments.read_with::<u64, _>(&mut 1, BE),
+        Err(Error::Incomplete)
+    );
+
+    let parts: [&[u8]; 2] = [b"abcd", b"efgh"];
+    let segments = Segments::new(&parts);
+    let ctx = Seq {
+        len: SeqLen::ToEnd,
+        max: 8,
+        ctx: BE,
+    };
+    assert_eq!(
+        segments.borrow_with::<Table<u8, Endian>, _>(&mut 0, ctx),
+        Err(Error::BadInput {
+            err: "Value spans segments"
+        })
+    );
+    #[cfg(feature = "alloc")]
+    assert_eq!(
+        segments.borrow_with::<std::borrow::Cow<[u8]>, _>(
+            &mut 0,
+            Seq {
+                len: SeqLen::ToEnd,
+                max: 8,
+                ctx: ()
+            }
+        ),
+        Err(Error::BadInput {
+            err: "Value spans segments"
+        })
+    );
+    let table: Table<u8, Endian> = segments.borrow_with(&mut 4, ctx).unwrap();
+    assert_eq!(table.len(), 4);
+
+    let long = [0u8; 100];
+    let parts = [&long[..50], &long[50..]];
+    let segments = Segments::new(&parts);
+    assert_eq!(segments.read_with::<u64, _>(&mut 46, LE).unwrap(), 0);
+    #[cfg(feature = "alloc")]
+    assert_eq!(
+        segments.read_with::<String, _>(&mut 0, Str::Len(70)),
+        Err(Error::BadInput {
+            err: "Value is too long to copy across segments"
+        })
+    );
+    #[cfg(feature = "alloc")]
+    {
+        let ctx = Seq {
+            len: SeqLen::ToEnd,
+            max: 100,
+            ctx: BE,
+        };
+        let parts = [&long[..40], &long[40..80]];
+        let segments = Segments::new(&parts);
+        assert_eq!(
+            segments.read_with::<Vec<u8>, _>(&mut 0, ctx),
+            Err(Error::BadInput {
+                err: "Value is too long to copy across segments"
+            })
+        );
+        let offset = &mut 20;
+        assert_eq!(
+            segments.read_with::<Vec<u8>, _>(offset, ctx).unwrap().len(),
+            60
+        );
+        assert_eq!(*offset, 80);
+    }
+
+    let segments = Segments::new(&[]);
+    assert!(segments.is_empty());
+    assert_eq!(segments.read::<bool, _>(&mut 0), Err(Error::Incomplete));
+}
+
+#[test]
+fn test_gather() {
+    struct Frame<'a> {
+        tag: u8,
+        header: &'a [u8],
+        body: &'a [u8],
+        name: &'a str,
+    }
+
+    impl<'a> TryWriteTo<'a, Endian> for Frame<'a> {
+        fn try_write_to<S: Sink<'a>>(self, sink: &mut S, endian: Endian) -> Result<()> {
+            sink.put_with(self.tag, endian)?;
+            sink.put_with(self.body.len() as u32, endian)?;
+            sink.put_bytes(self.header)?;
+            sink.put_bytes(self.body)?;
+            sink.put_with(Encoded(self.name), Str::Delimiter(NULL))
+        }
+    }
+
+    let body = [0xbb; 32];
+    let frame = || Frame {
+        tag: 7,
+        header: b"hd",
+        body: &body,
+        name: "x",
+    };
+
+    let mut bytes = [0u8; 41];
+    let mut writer = SliceWriter::new(&mut bytes);
+    frame().try_write_to(&mut writer, BE).unwrap();
+    assert_eq!(writer.len(), 41);
+    assert_eq!(bytes[..7], [7, 0, 0, 0, 32, b'h', b'd']);
+    assert_eq!(bytes[7..39], body);
+    assert_eq!(bytes[39..], [b'x', 0]);
+
+    let mut segments = [&[][..]; 3];
+    let mut scratch = [0u8; 16];
+    let mut writer = GatherWriter::new(&mut segments, &mut scratch, 16);
+    frame().try_write_to(&mut writer, BE).unwrap();
+    assert_eq!(writer.len(), 41);
+    let segments = writer.finish().unwrap();
+    assert_eq!(segments, [&bytes[..7], &body[..], &bytes[39..]]);
+    assert_eq!(segments[1].as_ptr(), body.as_ptr());
+
+    let mut parts = [0u8; 41];
+    let offset = &mut 0;
+    for segment in segments {
+        parts.write_with(offset, *segment, ()).unwrap();
+    }
+    assert_eq!(parts, bytes);
+
+    let mut segments = [&[][..]; 2];
+    let mut scratch = [0u8; 16];
+    let mut writer = GatherWriter::new(&mut segments, &mut scratch, 16);
+    frame().try_write_to(&mut writer, BE).unwrap();
+    assert_eq!(
+        writer.finish(),
+        Err(Error::BadInput {
+            err: "Too many segments"
+        })
+    );
+
+    let mut segments = [&[][..]; 3];
+    let mut scratch = [0u8; 8];
+    let mut writer = GatherWriter::new(&mut segments, &mut scratch, 1);
+    writer.put_bytes(&body[..4]).unwrap();
+    assert_eq!(writer.put_with(0u64, LE), Ok(()));
+    assert_eq!(writer.put_with(0u8, LE), Err(Error::Incomplete));
+    assert_eq!(writer.finish().unwrap().len(), 2);
+
+    let mut bytes = [0u8; 40];
+    let mut writer = SliceWriter::new(&mut bytes);
+    assert_eq!(
+        frame().try_write_to(&mut writer, BE),
+        Err(Error::Incomplete)
+    );
+
+    let header = || Header {
+        name: "HELLO",
+        enabled: true,
+    };
+    let mut segments = [&[][..]; 2];
+    let mut scratch = [0u8; 16];
+    let mut writer = GatherWriter::new(&mut segments, &mut scratch, 1);
+    Copied(header()).try_write_to(&mut writer, BE).unwrap();
+    writer.put_bytes(&body[..4]).unwrap();
+    assert_eq!(writer.len(), 12);
+    let segments = writer.finish().unwrap();
+    assert_eq!(
+        segments,
+        [&[0, 5, b'H', b'E', b'L', b'L', b'O', 0xff][..], &body[..4]]
+    );
+
+    let mut bytes = [0u8; 8];
+    let mut writer = SliceWriter::new(&mut bytes);
+    Copied(header()).try_write_to(&mut writer, BE).unwrap();
+    assert_eq!(bytes, *segments[0]);
+}
+
+#[test]
+fn test_encode() {
+    struct Pair {
+        a: u16,
+        b: Option<f32>,
+    }
+
+    impl Encode<Endian> for Pair {
+        fn encode(&self, bytes: &mut [u8], endian: Endian) -> Result<usize> {
+            let offset = &mut 0;
+            bytes.write_with(offset, self.a, endian)?;
+            bytes.write_with(offset, self.b, Optional::Flag(endian))?;
+            Ok(*offset)
+        }
+    }
+
+    let pair = Pair {
+        a: 0x0102,
+        b: Some(1.0),
+    };
+    let mut bytes = [0u8; 7];
+    assert_eq!(TryWrite::try_write(&pair, &mut bytes, BE), Ok(7));
+    assert_eq!(bytes, [0x01, 0x02, 0xff, 0x3f, 0x80, 0x00, 0x00]);
+    assert_eq!(pair.encode(&mut bytes, LE), Ok(7));
+    assert_eq!(bytes, [0x02, 0x01, 0xff, 0x00, 0x00, 0x80, 0x3f]);
+    assert_eq!(pair.encode(&mut bytes[..6], LE), Err(Error::Incomplete));
+
+    struct Wrapper {
+        pairs: [Pair; 2],
+    }
+
+    impl Encode<Endian> for Wrapper {
+        fn encode(&self, bytes: &mut [u8], endian: Endian) -> Result<usize> {
+            let offset = &mut 0;
+            for pair in &self.pairs {
+                bytes.write_with(offset, pair, endian)?;
+            }
+            Ok(*offset)
+        }
+    }
+
+    let wrapper = Wrapper {
+        pairs: [pair, Pair { a: 3, b: None }],
+    };
+    let mut bytes = [0u8; 10];
+    bytes.write_with(&mut 0, &wrapper, BE).unwrap();
+    assert_eq!(
+        bytes,
+        [0x01, 0x02, 0xff, 0x3f, 0x80, 0x00, 0x00, 0x00, 0x03, 0x00]
+    );
+    assert_eq!(true.encode(&mut bytes, ()), Ok(1));
+    assert_eq!(bytes[0], 0xff);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_encode_alloc() {
+    let list = vec![Some(1u8), None];
+    let ctx = Seq {
+        len: SeqLen::CountPrefix(Prefix::U8),
+        max: 4,
+        ctx: Optional::Flag(BE),
+    };
+
+    let mut bytes = [0u8; 4];
+    assert_eq!(TryWrite::try_write(&list, &mut bytes, ctx), Ok(4));
+    assert_eq!(bytes, [0x02, 0xff, 0x01, 0x00]);
+    assert_eq!(
+        bytes.read_with::<Vec<Option<u8>>>(&mut 0, ctx).unwrap(),
+        list
+    );
+
+    let name = Box::new(String::from("ab"));
+    let mut bytes = [0u8; 3];
+    assert_eq!(name.encode(&mut bytes, Str::Delimiter(NULL)), Ok(3));
+    assert_eq!(&bytes, b"ab\0");
+}
+
+#[test]
+fn test_read_into() {
+    let bytes: &[u8] = &[0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0x01, 0x01];
+
+    let mut nums = [[0u16; 2]; 2];
+    let offset = &mut 0;
+    bytes.read_into_with(offset, &mut nums, BE).unwrap();
+    assert_eq!(nums, [[1, 2], [3, 0x0101]]);
+    assert_eq!(*offset, 8);
+
+    let mut flag = false;
+    bytes.read_into(&mut 6, &mut flag).unwrap();
+    assert!(flag);
+
+    let mut num = 0u32;
+    assert_eq!(
+        bytes.read_into_with(&mut 6, &mut num, LE),
+        Err(Error::Incomplete)
+    );
+    assert_eq!(
+        bytes.read_into_with(&mut 9, &mut num, LE),
+        Err(Error::BadOffset(9))
+    );
+    assert_eq!(num, 0);
+
+    let slice: &mut [f32] = &mut [0.0; 1];
+    assert_eq!(slice.try_read_into(&[0x00, 0x00, 0x80, 0x3f], LE), Ok(4));
+    assert_eq!(slice, [1.0]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_read_into_alloc() {
+    let bytes: &[u8] = b"\x02ab\x03cde";
+    let ctx = Seq {
+        len: SeqLen::BytesPrefix(Prefix::U8),
+        max: 8,
+        ctx: (),
+    };
+
+    let mut str = String::with_capacity(16);
+    let ptr = str.as_ptr();
+    let offset = &mut 0;
+    bytes.read_into_with(offset, &mut str, ctx).unwrap();
+    assert_eq!(str, "ab");
+    bytes.read_into_with(offset, &mut str, ctx).unwrap();
+    assert_eq!(str, "cde");
+    assert_eq!(str.as_ptr(), ptr);
+    bytes.read_into_with(&mut 1, &mut str, Str::Len(2)).unwrap();
+    assert_eq!(str, "ab");
+
+    let mut list: Vec<u8> = Vec::with_capacity(16);
+    let ptr = list.as_ptr();
+    let ctx = Seq {
+        len: SeqLen::CountPrefix(Prefix::U8),
+        max: 8,
+        ctx: BE,
+    };
+    bytes.read_into_with(&mut 3, &mut list, ctx).unwrap();
+    assert_eq!(list, b"cde");
+    bytes.read_into_with(&mut 0, &mut list, ctx).unwrap();
+    assert_eq!(list, b"ab");
+    assert_eq!(list.as_ptr(), ptr);
+
+    let bytes: &[u8] = b"\x02ab\x03cde\x01f";
+    let ctx = Seq {
+        len: SeqLen::ToEnd,
+        max: 8,
+        ctx: Seq {
+            len: SeqLen::BytesPrefix(Prefix::U8),
+            max: 8,
+            ctx: (),
+        },
+    };
+    let mut names = vec![String::with_capacity(16), String::with_capacity(16)];
+    let ptrs = [names[0].as_ptr(), names[1].as_ptr()];
+    bytes.read_into_with(&mut 0, &mut names, ctx).unwrap();
+    assert_eq!(names, ["ab", "cde", "f"]);
+    assert_eq!([names[0].as_ptr(), names[1].as_ptr()], ptrs);
+    bytes.read_into_with(&mut 3, &mut names, ctx).unwrap();
+    assert_eq!(names, ["cde", "f"]);
+    assert_eq!([names[0].as_ptr(), names[1].as_ptr()], ptrs);
+}
+
+#[test]
+fn test_bytes_macro() {
+    const EMPTY: [u8; 0] = bytes!();
+    const MAGIC: u32 = 0xcafebabe;
+    const HEAD: [u8; 15] = bytes!(
+        u32 BE MAGIC,
+        i8 -2,
+        i16 LE -2,
+        "\u{e9}",
+        [0xaa; 2],
+        f32 BE 1.0,
+    );
+
+    assert!(EMPTY.is_empty());
+    assert_eq!(
+        HEAD,
+        [0xca, 0xfe, 0xba, 0xbe, 0xfe, 0xfe, 0xff, 0xc3, 0xa9, 0xaa, 0xaa, 0x3f, 0x80, 0, 0]
+    );
+
+    let i = 3;
+    let flag = true;
+    let bytes = bytes!(u8 i, bool flag, u64 LE i as u64, [i; 2]);
+    assert_eq!(bytes.len(), 12);
+    assert_eq!(bytes[..3], [3, 0xff, 3]);
+    assert_eq!(bytes[10..], [3, 3]);
+}
+
+#[test]
+fn test_read_macro() {
+    fn parse<'a>(bytes: &'a [u8], offset: &mut usize) -> Result<(u8, &'a [u8], u32, u16)> {
+        read!(bytes, offset;
+            count: u8,
+            data: &[u8] = Bytes::Len(count as usize),
+            tag: u32 = LE,
+            crc: u16 = BE
+        );
+        Ok((count, data, tag, crc))
+    }
+
+    let bytes = bytes!(u8 2, [0xaa; 2], u32 LE 7, u16 BE 0x1234, u8 0);
+
+    let offset = &mut 0;
+    assert_eq!(parse(&bytes, offset), Ok((2, &[0xaa, 0xaa][..], 7, 0x1234)));
+    assert_eq!(*offset, 9);
+    assert_eq!(parse(&bytes, offset), Err(Error::Incomplete));
+    assert_eq!(*offset, 9);
+
+    let offset = &mut 0;
+    assert_eq!(parse(&bytes[..8], offset), Err(Error::Incomplete));
+    assert_eq!(*offset, 0);
+    assert_eq!(parse(&bytes, &mut 11), Err(Error::BadOffset(11)));
+
+    #[derive(Debug, PartialEq)]
+    enum ParseError {
+        Byte(Error),
+    }
+
+    impl From<Error> for ParseError {
+        fn from(err: Error) -> Self {
+            ParseError::Byte(err)
+        }
+    }
+
+    fn parse_str(bytes: &[u8]) -> std::result::Result<&str, ParseError> {
+        let mut offset = 0;
+        read!(bytes, &mut offset; name: &str = Str::Delimiter(NULL));
+        Ok(name)
+    }
+
+    assert_eq!(parse_str(b"ab\0"), Ok("ab"));
+    assert_eq!(parse_str(b"ab"), Err(ParseError::Byte(Error::Incomplete)));
+}
+
+#[test]
+fn test_magic() {
+    let bytes = bytes!("RIFF", u32 LE 4, "WAVE", i16 BE -1);
+
+    let offset = &mut 0;
+    bytes.read_with::<()>(offset, Magic(b"RIFF")).unwrap();
+    assert_eq!(bytes.read_with::<u32>(offset, LE).unwrap(), 4);
+    assert_eq!(
+        bytes.read_with::<()>(offset, Magic(b"WAVf")),
+        Err(Error::BadMagic {
+            offset: 3,
+            expected: b'f',
+            found: b'E'
+        })
+    );
+    assert_eq!(*offset, 8);
+    bytes.read_with::<()>(offset, Magic(b"WAVE")).unwrap();
+    assert_eq!(
+        bytes.read_with::<()>(offset, Const(-2i16, BE)),
+        Err(Error::BadConst {
+            expected: 0xfffe,
+            found: 0xffff
+        })
+    );
+    bytes.read_with::<()>(offset, Const(-1i16, BE)).unwrap();
+    assert_eq!(*offset, 14);
+    assert_eq!(
+        bytes.read_with::<()>(&mut 12, Magic(b"\xff\xff\x00")),
+        Err(Error::Incomplete)
+    );
+    assert_eq!(
+        bytes.read_with::<()>(&mut 12, Const(0u32, BE)),
+        Err(Error::Incomplete)
+    );
+
+    let mut write = [0u8; 14];
+    let offset = &mut 0;
+    write.write_with(offset, (), Magic(b"RIFF")).unwrap();
+    write.write_with(offset, 4u32, LE).unwrap();
+    write.write_with(offset, (), Magic(b"WAVE")).unwrap();
+    write.write_with(offset, (), Const(-1i16, BE)).unwrap();
+    assert_eq!(write, bytes);
+    assert_eq!(
+        write.write_with(&mut 12, (), Const(0u32, BE)),
+        Err(Error::Incomplete)
+    );
+
+    assert_eq!(
+        Error::BadMagic {
+            offset: 3,
+            expected: b'f',
+            found: b'E'
+        }
+        .to_string(),
+        "bad magic at byte 3: expected 0x66, found 0x45"
+    );
+}
+
+#[test]
+fn test_half() {
+    // Every value survives a round trip through f32 and f64
+    for bits in 0..=u16::MAX {
+        let half = F16::from_bits(bits);
+        let bf16 = BF16::from_bits(bits);
+        if half.is_nan() {
+            assert!(F16::from_f32(half.to_f32()).is_nan());
+        } else {
+            assert_eq!(F16::from_f32(half.to_f32()).to_bits(), bits);
+            assert_eq!(F16::from_f64(half.to_f64()).to_bits(), bits);
+        }
+        if bf16.is_nan() {
+            assert!(BF16::from_f64(bf16.to_f64()).is_nan());
+        } else {
+            assert_eq!(BF16::from_f32(bf16.to_f32()).to_bits(), bits);
+            assert_eq!(BF16::from_f64(bf16.to_f64()).to_bits(), bits);
+        }
+    }
+
+    // bfloat16 is a rounded f32
+    for bits in (0..=u32::MAX).step_by(997) {
+        let x = f32::from_bits(bits);
+        let expected = ((u64::from(bits) + 0x7fff + u64::from((bits >> 16) & 1)) >> 16) as u16;
+        if x.is_nan() {
+            assert!(BF16::from_f32(x).is_nan());
+        } else {
+            assert_eq!(BF16::from_f32(x).to_bits(), expected, "{}", x);
+        }
+    }
+
+    let cases: &[(f64, u16)] = &[
+        (0.0, 0x0000),
+        (-0.0, 0x8000),
+        (1.0, 0x3c00),
+        (-2.0, 0xc000),
+        (65504.0, 0x7bff),
+        (65519.0, 0x7bff),
+        (65520.0, 0x7c00),
+        (1e10, 0x7c00),
+        (f64::NEG_INFINITY, 0xfc00),
+        (5.960464477539063e-8, 0x0001),
+        (2.9802322387695312e-8, 0x0000),
+        (2.98023224e-8, 0x0001),
+        (8.940696716308594e-8, 0x0002),
+        (6.097555160522461e-5, 0x03ff),
+        (6.100535392761231e-5, 0x0400),
+        (1e-300, 0x0000),
+        (1.0 + 1.0 / 2048.0, 0x3c00),
+        (1.0 + 3.0 / 2048.0, 0x3c02),
+        (1.0 + 1.0 / 2048.0 + 1.0 / 1099511627776.0, 0x3c01),
+    ];
+    for &(x, bits) in cases {
+        assert_eq!(F16::from_f64(x).to_bits(), bits, "{}", x);
+    }
+    assert_eq!(F16::from_bits(0x0001).to_f64(), 5.960464477539063e-8);
+    assert_eq!(F16::from_bits(0x8001).to_f32(), -5.9604645e-8);
+    assert_eq!(BF16::from_bits(0x0001).to_f64(), 9.183549615799121e-41);
+    assert!(F16::from_f32(f32::NAN).is_nan());
+    assert!(F16::NAN.to_f64().is_nan());
+    assert!(!F16::INFINITY.is_nan());
+    assert_eq!(F16::from_bits(0x7e01).to_f32().to_bits(), 0x7fc02000);
+    assert_eq!(F16::from_f32(f32::from_bits(0x7fc02000)).to_bits(), 0x7e01);
+    assert_eq!(BF16::NEG_INFINITY.to_f32(), f32::NEG_INFINITY);
+    assert_ne!(F16::NAN, F16::NAN);
+    assert_eq!(F16::from_f32(0.0), F16::from_f32(-0.0));
+    assert!(BF16::from_f32(1.0) < BF16::from_f32(1.5));
+    assert_eq!(
+        format!("{} {:?}", F16::from_f32(0.5), BF16::from_f32(-3.0)),
+        "0.5 -3.0"
+    );
+
+    let bytes = bytes!(u16 BE 0x3c00, u16 LE 0x3f80);
+    let offset = &mut 0;
+    assert_eq!(bytes.read_with::<F16>(offset, BE).unwrap().to_f32(), 1.0);
+    assert_eq!(bytes.read_with::<BF16>(offset, LE).unwrap().to_f32(), 1.0);
+    assert_eq!(bytes.read_with::<F16>(offset, BE), Err(Error::Incomplete));
+
+    let mut write = [0u8; 4];
+    let offset = &mut 0;
+    write.write_with(offset, F16::from_f32(1.0), BE).unwrap();
+    write.write_with(offset, BF16::from_f32(1.0), LE).unwrap();
+    assert_eq!(write, bytes);
+    let mut half = F16::default();
+    write.read_into_with(&mut 0, &mut half, BE).unwrap();
+    assert_eq!(half.to_f32(), 1.0);
+}
+
+#[test]
+fn test_fixed() {
+    use Overflow::Saturate;
+    use Rounding::*;
+
+    let q15 = |x, rounding| Q15::from_f64(x, rounding, Overflow::Error).map(Q15::to_bits);
+    assert_eq!(q15(0.5, Nearest), Ok(0x4000));
+    assert_eq!(q15(-1.0, Nearest), Ok(-0x8000));
+    assert_eq!(q15(1.5 / 32768.0, Nearest), Ok(2));
+    assert_eq!(q15(2.5 / 32768.0, Nearest), Ok(2));
+    assert_eq!(q15(-2.5 / 32768.0, Nearest), Ok(-2));
+    assert_eq!(q15(2.6 / 32768.0, Nearest), Ok(3));
+    assert_eq!(q15(2.5 / 32768.0, Floor), Ok(2));
+    assert_eq!(q15(-2.5 / 32768.0, Floor), Ok(-3));
+    assert_eq!(q15(2.5 / 32768.0, Ceil), Ok(3));
+    assert_eq!(q15(-2.5 / 32768.0, Ceil), Ok(-2));
+    assert_eq!(q15(-2.5 / 32768.0, Zero), Ok(-2));
+    assert_eq!(q15(-0.0, Nearest), Ok(0));
+    assert_eq!(
+        q15(1.0, Nearest),
+        Err(Error::Invalid {
+            err: "Value is out of fixed-point range"
+        })
+    );
+    assert_eq!(q15(32767.5 / 32768.0, Floor), Ok(0x7fff));
+    assert!(q15(32767.5 / 32768.0, Ceil).is_err());
+    assert!(q15(-1.0001, Nearest).is_err());
+    assert_eq!(
+        q15(f64::NAN, Nearest),
+        Err(Error::Invalid {
+            err: "Value is NaN"
+        })
+    );
+    assert_eq!(Q15::from_f64(1e9, Nearest, Saturate), Ok(Q15::MAX));
+    assert_eq!(
+        Q15::from_f64(f64::NEG_INFINITY, Nearest, Saturate),
+        Ok(Q15::MIN)
+    );
+    assert!(Q15::from_f64(f64::NAN, Nearest, Saturate).is_err());
+
+    type U64 = Fixed<u64, 0>;
+    assert_eq!(
+        U64::from_f64(1.8446744073709552e19, Nearest, Saturate),
+        Ok(U64::MAX)
+    );
+    assert!(U64::from_f64(1.8446744073709552e19, Nearest, Overflow::Error).is_err());
+    assert_eq!(
+        U64::from_f64((u64::MAX - 2047) as f64, Nearest, Overflow::Error).map(U64::to_bits),
+        Ok(18446744073709549568)
+    );
+    assert_eq!(
+        U64::from_f64(-0.4, Nearest, Overflow::Error).map(U64::to_bits),
+        Ok(0)
+    );
+    assert_eq!(
+        Fixed::<i64, 0>::from_f64(i64::MIN as f64, Floor, Overflow::Error),
+        Ok(Fixed::<i64, 0>::MIN)
+    );
+    assert_eq!(
+        Fixed::<i64, 0>::from_f64(4503599627370497.0, Nearest, Overflow::Error)
+            .map(Fixed::<i64, 0>::to_bits),
+        Ok(4503599627370497)
+    );
+
+    type UQ16 = Fixed<u16, 16>;
+    assert_eq!(UQ16::from_bits(0x8000).to_f32(), 0.5);
+    assert_eq!(
+        UQ16::from_f32(0.25, Nearest, Overflow::Error),
+        Ok(UQ16::from_bits(0x4000))
+    );
+    assert_eq!(Q16_16::from_bits(-0x18000).to_f64(), -1.5);
+    assert_eq!(Q31::MIN.to_f64(), -1.0);
+    assert_eq!(Q31::MAX.to_f32(), 1.0);
+    assert_eq!(Q15::from_bits(0x2000).to_string(), "0.25");
+
+    let bytes = bytes!(i16 BE 0x4000, u32 LE 0x00018000);
+    let offset = &mut 0;
+    assert_eq!(bytes.read_with::<Q15>(offset, BE).unwrap().to_f32(), 0.5);
+    assert_eq!(bytes.read_with::<Q16_16>(offset, LE).unwrap().to_f64(), 1.5);
+    assert_eq!(bytes.read_with::<Q15>(offset, BE), Err(Error::Incomplete));
+
+    let mut write = [0u8; 6];
+    let offset = &mut 0;
+    write
+        .write_with(offset, Q15::from_bits(0x4000), BE)
+        .unwrap();
+    write
+        .write_with(offset, Q16_16::from_bits(0x18000), LE)
+        .unwrap();
+    assert_eq!(write, bytes);
+    let mut value = Q15::default();
+    write.read_into_with(&mut 0, &mut value, BE).unwrap();
+    assert_eq!(value.to_bits(), 0x4000);
+}
+
+#[test]
+fn test_mixed() {
+    let bytes: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+
+    let read32 = |order| bytes.read_with::<Mixed<u32>>(&mut 0, order).unwrap().0;
+    assert_eq!(read32(Order::ABCD), 0x01020304);
+    assert_eq!(read32(Order::DCBA), 0x04030201);
+    assert_eq!(read32(Order::BADC), 0x02010403);
+    assert_eq!(read32(Order::CDAB), 0x03040102);
+
+    let read64 = |order| bytes.read_with::<Mixed<u64>>(&mut 0, order).unwrap().0;
+    assert_eq!(read64(Order::ABCD), 0x0102030405060708);
+    assert_eq!(read64(Order::DCBA), 0x0807060504030201);
+    assert_eq!(read64(Order::BADC), 0x0201040306050807);
+    assert_eq!(read64(Order::CDAB), 0x0708050603040102);
+
+    let offset = &mut 0;
+    let value: Mixed<i32> = bytes.read_with(offset, Order::DCBA).unwrap();
+    assert_eq!(
+        value.into_inner(),
+        bytes.read_with::<i32>(&mut 0, LE).unwrap()
+    );
+    assert_eq!(*offset, 4);
+    assert_eq!(
+        bytes.read_with::<Mixed<u64>>(offset, Order::ABCD),
+        Err(Error::Incomplete)
+    );
+
+    let swapped = Order::Custom([4, 5, 6, 7, 0, 1, 2, 3]);
+    assert_eq!(read64(swapped), 0x0506070801020304);
+    assert_eq!(
+        read32(Order::Custom([2, 3, 0, 1, 0, 0, 0, 0])),
+        read32(Order::CDAB)
+    );
+    assert_eq!(
+        read64(Order::Custom([1, 0, 3, 2, 5, 4, 7, 6])),
+        read64(Order::BADC)
+    );
+    let mut write = [0u8; 8];
+    write
+        .write_with(&mut 0, Mixed(0x0102030405060708u64), swapped)
+        .unwrap();
+    assert_eq!(write, [5, 6, 7, 8, 1, 2, 3, 4]);
+    let invalid = Error::BadInput {
+        err: "Invalid byte permutation",
+    };
+    assert_eq!(bytes.read_with::<Mixed<u32>>(&mut 0, swapped), Err(invalid));
+    assert_eq!(
+        bytes.read_with::<Mixed<u64>>(&mut 0, Order::Custom([0, 1, 2, 3, 4, 5, 6, 6])),
+        Err(invalid)
+    );
+    assert_eq!(
+        bytes.read_with::<Mixed<u32>>(&mut 0, Order::Custom([0, 1, 2, 4, 0, 0, 0, 0])),
+        Err(invalid)
+    );
+    assert_eq!(
+        write.write_with(&mut 0, Mixed(0u32), Order::Custom([0, 0, 1, 2, 3, 4, 5, 6])),
+        Err(invalid)
+    );
+    assert_eq!(write, [5, 6, 7, 8, 1, 2, 3, 4]);
+
+    let modbus = bytes!(u16 BE 0x0000, u16 BE 0x4120);
+    let value: Mixed<f32> = modbus.read_with(&mut 0, Order::CDAB).unwrap();
+    assert_eq!(*value, 10.0);
+
+    for &order in &[Order::ABCD, Order::DCBA, Order::BADC, Order::CDAB] {
+        let mut write = [0u8; 8];
+        write
+            .write_with(&mut 0, Mixed(read64(order)), order)
+            .unwrap();
+        assert_eq!(write, bytes);
+
+        let mut write = [0u8; 8];
+        let offset = &mut 0;
+        write.write_with(offset, Mixed(-1.5f64), order).unwrap();
+        assert_eq!(*offset, 8);
+        assert_eq!(*write.read_with::<Mixed<f64>>(&mut 0, order).unwrap(), -1.5);
+        assert_eq!(
+            write.write_with(&mut 6, Mixed(0i32), order),
+            Err(Error::Incomplete)
+        );
+    }
+}
+
+#[test]
+fn test_detect() {
+    let detect = |bytes: &[u8], ctx| bytes.read_with::<Endian>(&mut 0, ctx);
+
+    assert_eq!(detect(&[0xfe, 0xff, 0x00, 0x41], Detect::Bom), Ok(BE));
+    assert_eq!(detect(&[0xff, 0xfe, 0x41, 0x00], Detect::Bom), Ok(LE));
+    assert_eq!(detect(b"MM\0\x2a", Detect::Tiff), Ok(BE));
+    assert_eq!(detect(b"II\x2a\0", Detect::Tiff), Ok(LE));
+    assert_eq!(
+        detect(b"IM", Detect::Tiff),
+        Err(Error::BadInput {
+            err: "Unknown byte order mark"
+        })
+    );
+    assert_eq!(detect(b"I", Detect::Tiff), Err(Error::Incomplete));
+
+    assert_eq!(detect(&[0xca, 0xfe], Detect::Magic16(0xcafe)), Ok(BE));
+    assert_eq!(detect(&[0xfe, 0xca], Detect::Magic16(0xcafe)), Ok(LE));
+    assert_eq!(
+        detect(&[0xa1, 0xb2, 0xc3, 0xd4], Detect::Magic32(0xa1b2c3d4)),
+        Ok(BE)
+    );
+    assert_eq!(
+        detect(&[0xa1, 0xb2, 0x3c, 0x4d], Detect::Magic32(0xa1b2c3d4)),
+        Err(Error::BadConst {
+            expected: 0xa1b2c3d4,
+            found: 0xa1b23c4d
+        })
+    );
+    let magic = 0x0102030405060708;
+    assert_eq!(
+        detect(&[8, 7, 6, 5, 4, 3, 2, 1], Detect::Magic64(magic)),
+        Ok(LE)
+    );
+    assert_eq!(
+        detect(&[0x11, 0x11], Detect::Magic16(0x1111)),
+        Err(Error::BadInput {
+            err: "Magic reads the same in both byte orders"
+        })
+    );
+
+    let bytes = bytes!(u32 LE 0xa1b2c3d4, u16 LE 2, u16 LE 4);
+    let offset = &mut 0;
+    let endian = bytes
+        .read_with(offset, Detect::Magic32(0xa1b2c3d4))
+        .unwrap();
+    assert_eq!(bytes.read_with::<u16>(offset, endian), Ok(2));
+    assert_eq!(bytes.read_with::<u16>(offset, endian), Ok(4));
+
+    for &ctx in &[
+        Detect::Bom,
+        Detect::Tiff,
+        Detect::Magic16(0xcafe),
+        Detect::Magic32(0xa1b2c3d4),
+        Detect::Magic64(magic),
+    ] {
+        for &endian in &[BE, LE] {
+            let mut write = [0u8; 8];
+            write.write_with(&mut 0, endian, ctx).unwrap();
+            assert_eq!(detect(&write, ctx), Ok(endian));
+        }
+    }
+    assert_eq!(
+        [0u8; 3].write_with(&mut 0, BE, Detect::Magic32(0)),
+        Err(Error::Incomplete)
+    );
+}
+
+#[test]
+fn test_validate() {
+    let bytes = bytes!(u16 BE 1500, u16 BE 1501, u8 0x81, "ab");
+
+    let offset = &mut 0;
+    let mtu = Predicate(|len: &u16| *len <= 1500, "Length exceeds MTU");
+    let len: Validated<u16> = bytes.read_with(offset, Validate(BE, mtu)).unwrap();
+    assert_eq!(*len, 1500);
+    assert_eq!(
+        bytes.read_with::<Validated<u16>>(offset, Validate(BE, mtu)),
+        Err(Error::Invalid {
+            err: "Length exceeds MTU"
+        })
+    );
+    assert_eq!(*offset, 2);
+    assert_eq!(
+        bytes.read_with::<Validated<u16>>(offset, Validate(BE, 1..1501)),
+        Err(Error::Invalid {
+            err: "Value is out of range"
+        })
+    );
+    assert_eq!(
+        bytes
+            .read_with::<Validated<u16>>(offset, Validate(BE, (1..=1501, ..2000)))
+            .unwrap(),
+        Validated(1501)
+    );
+    assert_eq!(
+        bytes.read_with::<Validated<u8>>(offset, Validate(BE, Reserved(0x01u8))),
+        Err(Error::Invalid {
+            err: "Reserved bits are set"
+        })
+    );
+    assert!(bytes
+        .read_with::<Validated<u8>>(offset, Validate(BE, Reserved(0x7eu8)))
+        .is_ok());
+    let not_empty = Predicate(|s: &&str| !s.is_empty(), "Empty string");
+    let s: Validated<&str> = bytes
+        .read_with(offset, Validate(Str::Len(2), not_empty))
+        .unwrap();
+    assert_eq!(*s, "ab");
+    assert_eq!(
+        bytes.read_with::<Validated<&str>>(offset, Validate(Str::Len(0), not_empty)),
+        Err(Error::Invalid {
+            err: "Empty string"
+        })
+    );
+    assert_eq!(
+        bytes.read_with::<Validated<u16>>(offset, Validate(BE, 0..)),
+        Err(Error::Incomplete)
+    );
+
+    let mut write = [0u8; 4];
+    let offset = &mut 0;
+    write
+        .write_with(offset, Validated(1500u16), Validate(BE, mtu))
+        .unwrap();
+    assert_eq!(
+        write.write_with(offset, Validated(1501u16), Validate(BE, mtu)),
+        Err(Error::Invalid {
+            err: "Length exceeds MTU"
+        })
+    );
+    assert_eq!(*offset, 2);
+    assert_eq!(write, [0x05, 0xdc, 0, 0]);
+
+    assert_eq!(
+        Error::Invalid {
+            err: "Length exceeds MTU"
+        }
+        .to_string(),
+        "invalid value: Length exceeds MTU"
+    );
+}
+
+#[test]
+fn test_ptr() {
+    // Counts the hops of a chain of pointers until a null offset
+    struct Hops(usize);
+
+    impl<'a> TryRead<'a, &Base<'a>> for Hops {
+        fn try_read(bytes: &'a [u8], base: &Base<'a>) -> Result<(Self, usize)> {
+            let follow = Follow {
+                base,
+                origin: Origin::Base,
+                endian: BE,
+                ctx: base,
+            };
+            if bytes.read_with::<u16>(&mut 0, BE)? == 0 {
+                return Ok((Hops(0), 2));
+            }
+            let (next, size) = Ptr::<Hops, u16>::try_read(bytes, follow)?;
+            Ok((Hops(next.0 + 1), size))
+        }
+    }
+
+    let bytes = bytes!(u16 BE 2, u16 BE 4, u16 BE 0, u16 BE 6);
+    let base = Base::new(&bytes);
+    assert_eq!(bytes.read_with::<Hops>(&mut 0, &base).unwrap().0, 2);
+    assert_eq!(
+        bytes.read_with::<Hops>(&mut 6, &base).map(|hops| hops.0),
+        Err(Error::BadInput {
+            err: "Pointers nested too deeply"
+        })
+    );
+    assert_eq!(bytes.read_with::<Hops>(&mut 0, &base).unwrap().0, 2);
+
+    let shallow = Base::with_max_depth(&bytes, 1);
+    assert!(bytes.read_with::<Hops>(&mut 0, &shallow).is_err());
+    assert_eq!(bytes.read_with::<Hops>(&mut 2, &shallow).unwrap().0, 1);
+
+    let bytes = bytes!(u32 LE 10, i8 2, u8 0xff, "abc\0", u8 1, [0; 2]);
+    let base = Base::new(&bytes);
+    let str = Follow {
+        base: &base,
+        origin: Origin::Relative,
+        endian: LE,
+        ctx: Str::Delimiter(NULL),
+    };
+    let num = Follow {
+        base: &base,
+        origin: Origin::Relative,
+        endian: LE,
+        ctx: LE,
+    };
+    let name: Ptr<&str, i8> = bytes.read_with(&mut 4, str).unwrap();
+    assert_eq!(
+        name,
+        Ptr {
+            offset: 2,
+            value: "abc"
+        }
+    );
+    let name: Ptr<&str, u32> = bytes
+        .read_with(
+            &mut 0,
+            Follow {
+                origin: Origin::At(0),
+                ..str
+            },
+        )
+        .unwrap();
+    assert_eq!(name.into_inner(), "\x01");
+    assert_eq!(
+        bytes.read_with::<Ptr<u8>>(
+            &mut 0,
+            Follow {
+                origin: Origin::At(4),
+                ..num
+            }
+        ),
+        Err(Error::BadInput {
+            err: "Pointer out of bounds"
+        })
+    );
+    assert_eq!(
+        bytes.read_with::<Ptr<u16, u8>>(&mut 5, num),
+        Err(Error::BadInput {
+            err: "Pointer out of bounds"
+        })
+    );
+    assert_eq!(
+        bytes.read_with::<Ptr<u32, u8>>(
+            &mut 0,
+            Follow {
+                origin: Origin::Base,
+                ..num
+            }
+        ),
+        Err(Error::Incomplete)
+    );
+
+    assert_eq!(base.position(&bytes[6..8]), Ok(6));
+    assert_eq!(base.position(&bytes[13..]), Ok(13));
+    assert!(base.position(&[0u8; 2]).is_err());
+    assert!(Base::new(&bytes[..6]).position(&bytes[4..8]).is_err());
+}
+
+#[test]
+fn test_table() {
+    let bytes = bytes!(u16 BE 8, u32 BE 1, u32 BE 3, u32 BE 5, u8 0xff);
+    let seq = Seq {
+        len: SeqLen::BytesPrefix(Prefix::U16(BE)),
+        max: 4,
+        ctx: BE,
+    };
+
+    let offset = &mut 0;
+    let table: Table<u32, Endian> = bytes.read_with(offset, seq).unwrap();
+    assert_eq!(*offset, 10);
+    assert_eq!(table.len(), 2);
+    assert_eq!(table.as_bytes(), &bytes[2..10]);
+    assert_eq!(table.get(1), Ok(3));
+    assert_eq!(
+        table.get(2),
+        Err(Error::BadInput {
+            err: "Index out of bounds"
+        })
+    );
+    assert_eq!(table.iter().collect::<Result<Vec<_>>>(), Ok(vec![1, 3]));
+    assert_eq!(table.iter().next_back(), Some(Ok(3)));
+    assert_eq!(table.iter().len(), 2);
+    assert_eq!(table.binary_search_by_key(&3, |&v| v), Ok(Ok(1)));
+    assert_eq!(table.binary_search_by_key(&0, |&v| v), Ok(Err(0)));
+    assert_eq!(table.binary_search_by_key(&9, |&v| v), Ok(Err(2)));
+
+    let table: Table<u32, Endian> = bytes
+        .read_with(
+            &mut 2,
+            Seq {
+                len: SeqLen::Count(3),
+                ..seq
+            },
+        )
+        .unwrap();
+    let chunks = table.chunks(2).unwrap();
+    assert_eq!(chunks.len(), 2);
+    let lens: Vec<_> = chunks.map(|chunk| chunk.len()).collect();
+    assert_eq!(lens, [2, 1]);
+    assert_eq!(table.chunks(2).unwrap().nth(1).unwrap().get(0), Ok(5));
+    assert_eq!(
+        table.chunks(0).map(|chunks| chunks.len()),
+        Err(Error::BadInput {
+            err: "Chunk size is zero"
+        })
+    );
+
+    let table: Table<u16, Endian> = bytes
+        .read_with(
+            &mut 15,
+            Seq {
+                len: SeqLen::ToEnd,
+                ..seq
+            },
+        )
+        .unwrap();
+    assert!(table.is_empty());
+    assert_eq!(table.iter().next(), None);
+    assert_eq!(
+        table.chunks(1).unwrap().next().map(|chunk| chunk.len()),
+        None
+    );
+
+    assert_eq!(
+        bytes.read_with::<Table<u32, Endian>>(
+            &mut 2,
+            Seq {
+                len: SeqLen::Count(5),
+                ..seq
+            }
+        ),
+        Err(Error::BadInput {
+            err: "Too many elements"
+        })
+    );
+    assert_eq!(
+        bytes.read_with::<Table<u32, Endian>>(
+            &mut 6,
+            Seq {
+                len: SeqLen::Count(3),
+                ..seq
+            }
+        ),
+        Err(Error::Incomplete)
+    );
+    assert_eq!(
+        bytes.read_with::<Table<u32, Endian>>(
+            &mut 2,
+            Seq {
+                len: SeqLen::ToEnd,
+                ..seq
+            }
+        ),
+        Err(Error::BadInput {
+            err: "Length is not a multiple of record size"
+        })
+    );
+    assert!(Table::<bool>::new(&[1, 0, 1], ()).unwrap().get(2).unwrap());
 }
 
 #[test]
@@ -198,9 +2021,12 @@ fn test_bytes_pattern() {
     assert!(bytes
         .read_with::<&[u8]>(&mut 0, Bytes::PatternUntil(b"", 3))
         .is_err());
-    assert!(bytes
-        .read_with::<&[u8]>(&mut 0, Bytes::PatternUntil(b"abcd", 3))
-        .is_err());
+    assert_eq!(
+        bytes.read_with::<&[u8]>(&mut 0, Bytes::PatternUntil(b"abcd", 3)),
+        Err(Error::BadInput {
+            err: "Pattern is longer than restricted length"
+        })
+    );
     assert!(bytes
         .read_with::<&[u8]>(&mut 0, Bytes::PatternUntil(b"xyz", 20))
         .is_err());
@@ -217,6 +2043,98 @@ fn test_bytes_pattern() {
         .is_err());
 }
 
+#[test]
+fn test_bytes_delimiter() {
+    let bytes: &[u8] = b"abcdefghijk";
+
+    let mut offset = 0;
+    assert_eq!(
+        bytes
+            .read_with::<&[u8]>(&mut offset, Bytes::Delimiter(b"cd"))
+            .unwrap(),
+        b"ab"
+    );
+    assert_eq!(offset, 4);
+    assert_eq!(
+        TryRead::try_read(bytes, Bytes::Delimiter(b"jk")).unwrap(),
+        (&b"abcdefghi"[..], 11)
+    );
+    assert_eq!(
+        TryRead::try_read(bytes, Bytes::DelimiterUntil(b"cd", 4)).unwrap(),
+        (&b"ab"[..], 4)
+    );
+    assert_eq!(
+        TryRead::try_read(bytes, Bytes::DelimiterUntil(b"cd", 3)).unwrap(),
+        (&b"abc"[..], 3)
+    );
+    assert!(bytes
+        .read_with::<&[u8]>(&mut 0, Bytes::Delimiter(b"xyz"))
+        .is_err());
+    assert!(bytes
+        .read_with::<&[u8]>(&mut 0, Bytes::Delimiter(b""))
+        .is_err());
+    assert!(bytes
+        .read_with::<&[u8]>(&mut 0, Bytes::DelimiterUntil(b"xyz", 12))
+        .is_err());
+    assert_eq!(
+        bytes.read_with::<&[u8]>(&mut 0, Bytes::DelimiterUntil(b"xyz", 2)),
+        Err(Error::BadInput {
+            err: "Pattern is longer than restricted length"
+        })
+    );
+
+    // Patterns don't need to be `'static`
+    let pattern = String::from("--boundary");
+    let bytes: &[u8] = b"part one--boundarypart two";
+    assert_eq!(
+        bytes
+            .read_with::<&[u8]>(&mut 0, Bytes::Delimiter(pattern.as_bytes()))
+            .unwrap(),
+        b"part one"
+    );
+}
+
+#[test]
+fn test_delimiter_search() {
+    for len in 0..40 {
+        for position in 0..len {
+            let mut bytes = vec![b'a'; len];
+            bytes[position] = NULL;
+            if position + 1 < len {
+                bytes[len - 1] = NULL;
+            }
+            let mut offset = 0;
+            let str: &str = bytes.read_with(&mut offset, Str::Delimiter(NULL)).unwrap();
+            assert_eq!(str.len(), position);
+            assert_eq!(offset, position + 1);
+        }
+        let bytes = vec![0xff; len];
+        assert!(bytes
+            .read_with::<&[u8]>(&mut 0, Bytes::Delimiter(&[0xfe]))
+            .is_err());
+    }
+}
+
+quickcheck! {
+    fn test_pattern_search(bytes: Vec<u8>, pattern: Vec<u8>) -> bool {
+        // A small alphabet makes matches and periodic patterns likely.
+        let bytes: Vec<u8> = bytes.iter().map(|b| b % 3).collect();
+        let pattern: Vec<u8> = pattern.iter().take(6).map(|b| b % 3).collect();
+        if pattern.is_empty() {
+            return true;
+        }
+
+        let expected = bytes
+            .windows(pattern.len())
+            .position(|window| window == &pattern[..]);
+        let result = bytes
+            .read_with::<&[u8]>(&mut 0, Bytes::Delimiter(&pattern))
+            .ok()
+            .map(|sub| sub.len());
+        result == expected
+    }
+}
+
 #[test]
 fn test_bool() {
     let bytes = [0x00, 0x01, 0x80, 0xff];
@@ -232,6 +2150,57 @@ fn test_bool() {
     assert!(bytes[1] != 0);
 }
 
+#[test]
+fn test_option() {
+    let bytes: &[u8] = &[0x00, 0x02, 0x12, 0x34, 0x56, 0x78];
+
+    let mut offset = 0;
+    let value: Option<u32> = bytes.read_with(&mut offset, Optional::Flag(LE)).unwrap();
+    assert_eq!(value, None);
+    assert_eq!(offset, 1);
+    let value: Option<u32> = bytes.read_with(&mut offset, Optional::Flag(LE)).unwrap();
+    assert_eq!(value, Some(0x78563412));
+    assert_eq!(offset, 6);
+
+    let value: Option<u16> = bytes.read_with(&mut 2, Optional::When(true, BE)).unwrap();
+    assert_eq!(value, Some(0x1234));
+    let mut offset = 2;
+    let value: Option<u16> = bytes
+        .read_with(&mut offset, Optional::When(false, BE))
+        .unwrap();
+    assert_eq!(value, None);
+    assert_eq!(offset, 2);
+
+    assert!(bytes
+        .read_with::<Option<u32>>(&mut 2, Optional::Flag(LE))
+        .is_err());
+    assert!(bytes
+        .read_with::<Option<u32>>(&mut 4, Optional::When(true, LE))
+        .is_err());
+
+    let mut write = [0xaa; 4];
+    let mut offset = 0;
+    write
+        .write_with(&mut offset, Some(0x1234u16), Optional::Flag(BE))
+        .unwrap();
+    write
+        .write_with(&mut offset, None::<u16>, Optional::Flag(BE))
+        .unwrap();
+    assert_eq!(offset, 4);
+    assert_eq!(write, [0xff, 0x12, 0x34, 0x00]);
+
+    assert_eq!(
+        TryWrite::try_write(Some(true), &mut write, Optional::When(true, ())).unwrap(),
+        1
+    );
+    assert_eq!(
+        TryWrite::try_write(None::<bool>, &mut write, Optional::When(false, ())).unwrap(),
+        0
+    );
+    assert!(TryWrite::try_write(Some(true), &mut write, Optional::When(false, ())).is_err());
+    assert!(TryWrite::try_write(None::<bool>, &mut write, Optional::When(true, ())).is_err());
+}
+
 #[test]
 fn test_iter() {
     let bytes: &[u8] = b"hello\0world\0dead\0beef\0more";